@@ -1,4 +1,5 @@
 use cosmic_text::{SubpixelBin, SwashImage};
+use std::ops::Range;
 use std::sync::Arc;
 
 mod path;
@@ -16,6 +17,9 @@ use defs::*;
 mod paint;
 use paint::*;
 
+mod waveform;
+use waveform::*;
+
 mod gpu_vec;
 use gpu_vec::*;
 
@@ -38,16 +42,244 @@ struct Uniforms {
     atlas_size: [f32; 2],
 }
 
+/// Average number of line segments in each band of a polyline. Each
+/// fragment tests every segment of its band, so this trades fragment work
+/// for fewer prims.
+const POLYLINE_SEGMENTS: usize = 32;
+
+/// Number of segments in each prim of a filled area. Fragments search for
+/// their segment, so prims can be larger than for polylines.
+const AREA_SEGMENTS: usize = 64;
+
+/// How far wires sag below the line between their end points, as a
+/// fraction of the distance between them.
+const WIRE_SAG: f32 = 0.25;
+
 #[derive(Copy, Clone, Debug)]
 pub struct PaintIndex {
     index: usize,
 }
 
+/// Determines which areas of a path are inside.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// A point is inside if the path winds around it at all. Overlapping
+    /// contours going the same direction don't cut holes. This is the
+    /// default in SVG.
+    NonZero,
+
+    /// A point is inside if a ray from it crosses the path an odd number
+    /// of times.
+    EvenOdd,
+}
+
+/// Shape at the ends of open strokes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LineCap {
+    /// Ends flush with the end point.
+    Butt,
+
+    /// Semicircle centered on the end point.
+    Round,
+
+    /// Extends past the end point by half the stroke width.
+    Square,
+}
+
+/// Distance a stroke with the given cap reaches past its control points,
+/// with room left for antialiasing.
+fn cap_extent(width: f32, cap: LineCap) -> f32 {
+    match cap {
+        LineCap::Square => width * std::f32::consts::SQRT_2,
+        _ => width,
+    }
+}
+
+/// Where a stroke lies relative to the edge of a shape.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StrokeAlign {
+    /// Entirely inside the shape, so the shape doesn't grow.
+    Inside,
+
+    /// Centered on the edge.
+    Center,
+
+    /// Entirely outside the shape.
+    Outside,
+}
+
+impl StrokeAlign {
+    /// Distance from the edge to the center of the stroke, positive
+    /// outwards.
+    fn offset(self, width: f32) -> f32 {
+        match self {
+            StrokeAlign::Inside => -width / 2.0,
+            StrokeAlign::Center => 0.0,
+            StrokeAlign::Outside => width / 2.0,
+        }
+    }
+}
+
+/// Shape of the corners where segments of a stroke meet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LineJoin {
+    /// Sharp corner, unless it would be longer than the miter limit, in
+    /// which case it's beveled.
+    Miter,
+
+    /// Circular corner.
+    Round,
+
+    /// Corner cut off straight.
+    Bevel,
+}
+
+/// How path strokes are drawn.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StrokeStyle {
+    pub join: LineJoin,
+
+    /// Maximum ratio of miter length to stroke width before miter joins
+    /// are beveled.
+    pub miter_limit: f32,
+
+    pub cap: LineCap,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            join: LineJoin::Miter,
+            miter_limit: 10.0,
+            cap: LineCap::Butt,
+        }
+    }
+}
+
+/// Dash pattern of a stroke.
+///
+/// The pattern alternates the lengths of dashes and gaps, and is repeated
+/// twice if it has an odd number of entries. `phase` is the distance into
+/// the pattern at which the stroke starts. Dashes end with the cap of the
+/// stroke, so zero length dashes with round caps draw dots.
+///
+/// Empty patterns, patterns with negative lengths and patterns with no
+/// length at all draw solid strokes.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Dash<'a> {
+    pub pattern: &'a [f32],
+    pub phase: f32,
+}
+
+impl Dash<'static> {
+    /// No dashes.
+    pub const SOLID: Self = Self {
+        pattern: &[],
+        phase: 0.0,
+    };
+}
+
+impl<'a> Dash<'a> {
+    pub fn new(pattern: &'a [f32], phase: f32) -> Self {
+        Self { pattern, phase }
+    }
+}
+
+/// Radius of each corner of a rectangle.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    /// Radii of the rectangle grown by `spread` (or shrunk if negative).
+    /// Like CSS box shadows, sharp corners stay sharp.
+    fn spread(self, spread: f32) -> Self {
+        let f = |r: f32| {
+            if spread >= 0.0 && r < spread {
+                r + spread * (1.0 + (r / spread - 1.0).powi(3))
+            } else {
+                (r + spread).max(0.0)
+            }
+        };
+        Self {
+            top_left: f(self.top_left),
+            top_right: f(self.top_right),
+            bottom_right: f(self.bottom_right),
+            bottom_left: f(self.bottom_left),
+        }
+    }
+
+    fn to_array(self) -> [f32; 4] {
+        [
+            self.top_left,
+            self.top_right,
+            self.bottom_right,
+            self.bottom_left,
+        ]
+    }
+}
+
+impl From<f32> for CornerRadii {
+    fn from(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+}
+
+/// Width of each side of a border.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct BorderWidths {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl From<f32> for BorderWidths {
+    fn from(width: f32) -> Self {
+        Self {
+            top: width,
+            right: width,
+            bottom: width,
+            left: width,
+        }
+    }
+}
+
+/// CSS style box shadow.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct BoxShadow {
+    pub offset: LocalVector,
+
+    /// Standard deviation of the gaussian blur.
+    pub blur_radius: f32,
+
+    /// How much the shadow is grown (or shrunk if negative) from the box.
+    /// Shadows shrink no further than to a line through the center.
+    pub spread: f32,
+
+    /// Draws the shadow inside the box instead of outside.
+    pub inset: bool,
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct ImageIndex {
     index: usize,
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct WaveformIndex {
+    index: usize,
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct LineMetrics {
     pub glyph_start: usize,
@@ -77,6 +309,80 @@ impl Scissor {
     }
 }
 
+/// Shadow drawn beneath prims.
+#[derive(Copy, Clone, Debug)]
+struct Shadow {
+    offset: LocalVector,
+    blur_radius: f32,
+    paint: PaintIndex,
+}
+
+impl Shadow {
+    /// The shadow cast by a prim.
+    fn prim(&self, prim: &Prim) -> Prim {
+        let mut shadow = *prim;
+        shadow.paint = self.paint.index as u32;
+
+        shadow.set_blur(self.blur_radius);
+
+        // Grids store the index of their major paint, and borders their
+        // side paints. Shadows use their own paint for everything.
+        if prim.prim_type == PrimType::Grid as u32 || prim.prim_type == PrimType::Border as u32 {
+            shadow.count = 0;
+        }
+
+        if prim.prim_type == PrimType::ColorGlyph as u32 {
+            // Use the alpha of the glyph with the shadow color.
+            shadow.prim_type = PrimType::OverrideColorSvg as u32;
+        }
+
+        // Move the quad without moving the texture coordinates, so the
+        // shape is evaluated at the offset.
+        shadow.quad_bounds[0] += self.offset.x;
+        shadow.quad_bounds[1] += self.offset.y;
+        shadow.quad_bounds[2] += self.offset.x;
+        shadow.quad_bounds[3] += self.offset.y;
+
+        let extent = 3.0 * self.blur_radius;
+        widen(&mut shadow, LocalVector::new(extent, extent));
+        shadow
+    }
+}
+
+/// Widens the quad of a prim by `extent` on each side.
+fn widen(prim: &mut Prim, extent: LocalVector) {
+    // Texture coordinates may be scaled relative to the quad.
+    let quad_size = [
+        prim.quad_bounds[2] - prim.quad_bounds[0],
+        prim.quad_bounds[3] - prim.quad_bounds[1],
+    ];
+    let tex_size = [
+        prim.tex_bounds[2] - prim.tex_bounds[0],
+        prim.tex_bounds[3] - prim.tex_bounds[1],
+    ];
+    let scale = LocalVector::new(
+        if quad_size[0] > 0.0 {
+            tex_size[0] / quad_size[0]
+        } else {
+            1.0
+        },
+        if quad_size[1] > 0.0 {
+            tex_size[1] / quad_size[1]
+        } else {
+            1.0
+        },
+    );
+
+    prim.quad_bounds[0] -= extent.x;
+    prim.quad_bounds[1] -= extent.y;
+    prim.quad_bounds[2] += extent.x;
+    prim.quad_bounds[3] += extent.y;
+    prim.tex_bounds[0] -= extent.x * scale.x;
+    prim.tex_bounds[1] -= extent.y * scale.y;
+    prim.tex_bounds[2] += extent.x * scale.x;
+    prim.tex_bounds[3] += extent.y * scale.y;
+}
+
 pub struct Vger {
     device: Arc<wgpu::Device>,
     queue: Arc<wgpu::Queue>,
@@ -90,15 +396,22 @@ pub struct Vger {
     screen_size: ScreenSize,
     paint_count: usize,
     pipeline: wgpu::RenderPipeline,
+    mesh_pipeline: wgpu::RenderPipeline,
     uniform_bind_group: wgpu::BindGroup,
     uniforms: GPUVec<Uniforms>,
     xform_count: usize,
     scissor_count: usize,
     path_scanner: PathScanner,
+    path_tolerance: f32,
     pen: LocalPoint,
+    path_start: LocalPoint,
+    path_open: bool,
+    shadow_stack: Vec<Option<Shadow>>,
+    polyline_scanner: PolylineScanner,
     pub glyph_cache: GlyphCache,
     images: Vec<Option<wgpu::Texture>>,
     image_bind_groups: Vec<Option<wgpu::BindGroup>>,
+    waveforms: Vec<Option<Waveform>>,
     cache_bind_group_layout: wgpu::BindGroupLayout,
     cache_bind_group: wgpu::BindGroup,
 }
@@ -264,6 +577,36 @@ impl Vger {
             multiview: None,
         });
 
+        let mesh_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("vger mesh pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_mesh",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_mesh",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: texture_format,
+                    blend: Some(wgpu::BlendState {
+                        color: blend_comp,
+                        alpha: blend_comp,
+                    }),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                cull_mode: None,
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
         Self {
             device,
             queue,
@@ -277,15 +620,22 @@ impl Vger {
             screen_size: ScreenSize::new(512.0, 512.0),
             paint_count: 0,
             pipeline,
+            mesh_pipeline,
             uniforms,
             uniform_bind_group,
             xform_count: 0,
             scissor_count: 0,
             path_scanner: PathScanner::new(),
+            path_tolerance: DEFAULT_TOLERANCE,
             pen: LocalPoint::zero(),
+            path_start: LocalPoint::zero(),
+            path_open: false,
+            shadow_stack: vec![],
+            polyline_scanner: PolylineScanner::default(),
             glyph_cache,
             images: vec![],
             image_bind_groups: vec![],
+            waveforms: vec![],
             cache_bind_group_layout,
             cache_bind_group,
         }
@@ -333,6 +683,10 @@ impl Vger {
         self.add_xform();
         self.scissor_count = 0;
         self.pen = LocalPoint::zero();
+        self.path_start = LocalPoint::zero();
+        self.path_open = false;
+        self.shadow_stack.clear();
+        self.shadow_stack.push(None);
 
         // If we're getting close to full, reset the glyph cache.
         if self.glyph_cache.check_usage(&self.device) {
@@ -351,16 +705,18 @@ impl Vger {
         });
     }
 
-    /// Saves rendering state (transform and scissor rect).
+    /// Saves rendering state (transform, scissor rect and shadow).
     pub fn save(&mut self) {
         self.tx_stack.push(*self.tx_stack.last().unwrap());
         self.scissor_stack.push(*self.scissor_stack.last().unwrap());
+        self.shadow_stack.push(*self.shadow_stack.last().unwrap());
     }
 
-    /// Restores rendering state (transform and scissor rect).
+    /// Restores rendering state (transform, scissor rect and shadow).
     pub fn restore(&mut self) {
         self.tx_stack.pop();
         self.scissor_stack.pop();
+        self.shadow_stack.pop();
     }
 
     /// Encode all rendering to a command buffer.
@@ -398,6 +754,26 @@ impl Vger {
 
             for i in 0..n {
                 let prim = &scene.prims[self.cur_layer][i];
+
+                // Meshes use their own pipeline. Flush the prims before
+                // them so drawing order is preserved.
+                if prim.prim_type == PrimType::Mesh as u32 {
+                    if m > 0 {
+                        rpass.draw(
+                            /*vertices*/ 0..4,
+                            /*instances*/ start..(start + m),
+                        );
+                    }
+
+                    rpass.set_pipeline(&self.mesh_pipeline);
+                    rpass.draw(prim.start..(prim.start + prim.count), 0..1);
+                    rpass.set_pipeline(&self.pipeline);
+
+                    start += m + 1;
+                    m = 0;
+                    continue;
+                }
+
                 let image_id = scene.paints[prim.paint as usize].image;
 
                 // Image changed, render.
@@ -436,12 +812,69 @@ impl Vger {
         queue.submit(Some(encoder.finish()));
     }
 
-    fn render(&mut self, prim: Prim) {
-        let prims = self.scenes[self.cur_scene]
+    fn render(&mut self, mut prim: Prim) {
+        self.widen_for_paint(&mut prim);
+        self.render_shadow(&prim);
+        self.push_prim(prim);
+    }
+
+    /// Like `render`, without a shadow. Shapes split into several prims
+    /// use this, and cast their shadow with a single prim for the whole
+    /// shape so the blur isn't cut off where the prims meet.
+    fn render_unshadowed(&mut self, mut prim: Prim) {
+        self.widen_for_paint(&mut prim);
+        self.push_prim(prim);
+    }
+
+    /// Draws the current shadow of a prim, if any.
+    fn render_shadow(&mut self, prim: &Prim) {
+        if let Some(shadow) = self.shadow() {
+            self.push_prim(shadow.prim(prim));
+        }
+    }
+
+    /// Extent in local coordinates of a device pixel under the current
+    /// transform, for antialiasing margins.
+    fn pixel_extent(&self) -> LocalVector {
+        let px = 1.0 / self.device_px_ratio;
+        match self.tx_stack.last().unwrap().inverse() {
+            Some(inv) => {
+                let x = inv.transform_vector(euclid::Vector2D::new(px, 0.0));
+                let y = inv.transform_vector(euclid::Vector2D::new(0.0, px));
+                LocalVector::new(x.x.abs() + y.x.abs(), x.y.abs() + y.y.abs())
+            }
+            None => LocalVector::new(px, px),
+        }
+    }
+
+    fn widen_for_paint(&self, prim: &mut Prim) {
+        let glow = self.glow(PaintIndex {
+            index: prim.paint as usize,
+        });
+        if glow > 0.0 {
+            widen(prim, LocalVector::new(glow, glow));
+        }
+    }
+
+    fn glow(&self, paint_index: PaintIndex) -> f32 {
+        let paints = &self.scenes[self.cur_scene].paints;
+        if paint_index.index < paints.len() {
+            paints[paint_index.index].glow
+        } else {
+            0.0
+        }
+    }
+
+    fn push_prim(&mut self, prim: Prim) {
+        self.scenes[self.cur_scene]
             .depthed_prims
             .entry(self.cur_z_index)
-            .or_default();
-        prims.push(prim);
+            .or_default()
+            .push(prim);
+    }
+
+    fn shadow(&self) -> Option<Shadow> {
+        self.shadow_stack.last().copied().flatten()
     }
 
     /// Fills a circle.
@@ -465,67 +898,52 @@ impl Vger {
         self.render(prim);
     }
 
-    /// Strokes an arc.
-    pub fn stroke_arc<Pt: Into<LocalPoint>>(
+    /// Fills an axis aligned ellipse.
+    pub fn fill_ellipse<Pt: Into<LocalPoint>, Vec: Into<LocalVector>>(
         &mut self,
         center: Pt,
-        radius: f32,
-        width: f32,
-        rotation: f32,
-        aperture: f32,
+        radii: Vec,
         paint_index: PaintIndex,
     ) {
         let mut prim = Prim::default();
-        prim.prim_type = PrimType::Arc as u32;
-        prim.radius = radius;
+        prim.prim_type = PrimType::Ellipse as u32;
         let c: LocalPoint = center.into();
-        prim.cvs = [
-            c.x,
-            c.y,
-            rotation.sin(),
-            rotation.cos(),
-            aperture.sin(),
-            aperture.cos(),
-        ];
-        prim.width = width;
+        let r: LocalVector = radii.into();
+        prim.cvs[0] = c.x;
+        prim.cvs[1] = c.y;
+        prim.cvs[2] = r.x;
+        prim.cvs[3] = r.y;
         prim.paint = paint_index.index as u32;
-        prim.quad_bounds = [
-            c.x - radius - width,
-            c.y - radius - width,
-            c.x + radius + width,
-            c.y + radius + width,
-        ];
+        prim.quad_bounds = [c.x - r.x, c.y - r.y, c.x + r.x, c.y + r.y];
         prim.tex_bounds = prim.quad_bounds;
         prim.scissor = self.add_scissor() as u32;
 
         self.render(prim);
     }
 
-    /// Fills a rectangle.
-    pub fn fill_rect<Rect: Into<LocalRect>>(
+    /// Strokes an axis aligned ellipse.
+    pub fn stroke_ellipse<Pt: Into<LocalPoint>, Vec: Into<LocalVector>>(
         &mut self,
-        rect: Rect,
-        radius: f32,
+        center: Pt,
+        radii: Vec,
+        width: f32,
         paint_index: PaintIndex,
-        blur_radius: f32,
     ) {
         let mut prim = Prim::default();
-        prim.prim_type = PrimType::Rect as u32;
-        let r: LocalRect = rect.into();
-        let min = r.min();
-        let max = r.max();
-        prim.cvs[0] = min.x;
-        prim.cvs[1] = min.y;
-        prim.cvs[2] = max.x;
-        prim.cvs[3] = max.y;
-        prim.cvs[4] = blur_radius;
-        prim.radius = radius;
+        prim.prim_type = PrimType::EllipseStroke as u32;
+        let c: LocalPoint = center.into();
+        let r: LocalVector = radii.into();
+        prim.cvs[0] = c.x;
+        prim.cvs[1] = c.y;
+        prim.cvs[2] = r.x;
+        prim.cvs[3] = r.y;
+        prim.width = width;
         prim.paint = paint_index.index as u32;
         prim.quad_bounds = [
-            min.x - blur_radius * 3.0,
-            min.y - blur_radius * 3.0,
-            max.x + blur_radius * 3.0,
-            max.y + blur_radius * 3.0,
+            c.x - r.x - width,
+            c.y - r.y - width,
+            c.x + r.x + width,
+            c.y + r.y + width,
         ];
         prim.tex_bounds = prim.quad_bounds;
         prim.scissor = self.add_scissor() as u32;
@@ -533,54 +951,71 @@ impl Vger {
         self.render(prim);
     }
 
-    /// Strokes a rectangle.
-    pub fn stroke_rect(
+    /// Fills a sector of a ring between `inner_radius` and `outer_radius`.
+    /// An inner radius of zero fills a pie slice. Like `stroke_arc`, the
+    /// sector is centered on `rotation` and spans `aperture` radians to each
+    /// side. `cap` shapes the ends of the sector.
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_sector<Pt: Into<LocalPoint>>(
         &mut self,
-        min: LocalPoint,
-        max: LocalPoint,
-        radius: f32,
-        width: f32,
+        center: Pt,
+        inner_radius: f32,
+        outer_radius: f32,
+        rotation: f32,
+        aperture: f32,
+        cap: LineCap,
         paint_index: PaintIndex,
     ) {
         let mut prim = Prim::default();
-        prim.prim_type = PrimType::RectStroke as u32;
-        prim.cvs[0] = min.x;
-        prim.cvs[1] = min.y;
-        prim.cvs[2] = max.x;
-        prim.cvs[3] = max.y;
-        prim.radius = radius;
-        prim.width = width;
+        prim.prim_type = PrimType::Sector as u32;
+        let c: LocalPoint = center.into();
+        prim.cvs = [
+            c.x,
+            c.y,
+            rotation.sin(),
+            rotation.cos(),
+            aperture.sin(),
+            aperture.cos(),
+        ];
+        prim.radius = (inner_radius + outer_radius) / 2.0;
+        prim.width = outer_radius - inner_radius;
+        prim.set_cap(cap as u32);
         prim.paint = paint_index.index as u32;
-        prim.quad_bounds = [min.x - width, min.y - width, max.x + width, max.y + width];
+        let extent = match cap {
+            LineCap::Square => outer_radius + prim.width / 2.0,
+            _ => outer_radius,
+        };
+        prim.quad_bounds = [c.x - extent, c.y - extent, c.x + extent, c.y + extent];
         prim.tex_bounds = prim.quad_bounds;
         prim.scissor = self.add_scissor() as u32;
 
         self.render(prim);
     }
 
-    /// Strokes a line segment.
-    pub fn stroke_segment<Pt: Into<LocalPoint>>(
+    #[allow(clippy::too_many_arguments)]
+    fn polygon_prim(
         &mut self,
-        a: Pt,
-        b: Pt,
+        prim_type: PrimType,
+        center: LocalPoint,
+        radius: f32,
+        sides: u32,
+        rotation: f32,
+        shape: f32,
         width: f32,
         paint_index: PaintIndex,
     ) {
         let mut prim = Prim::default();
-        prim.prim_type = PrimType::Segment as u32;
-        let ap: LocalPoint = a.into();
-        let bp: LocalPoint = b.into();
-        prim.cvs[0] = ap.x;
-        prim.cvs[1] = ap.y;
-        prim.cvs[2] = bp.x;
-        prim.cvs[3] = bp.y;
+        prim.prim_type = prim_type as u32;
+        prim.cvs = [center.x, center.y, sides as f32, rotation, shape, 0.0];
+        prim.radius = radius;
         prim.width = width;
         prim.paint = paint_index.index as u32;
+        let extent = radius + width;
         prim.quad_bounds = [
-            ap.x.min(bp.x) - width * 2.0,
-            ap.y.min(bp.y) - width * 2.0,
-            ap.x.max(bp.x) + width * 2.0,
-            ap.y.max(bp.y) + width * 2.0,
+            center.x - extent,
+            center.y - extent,
+            center.x + extent,
+            center.y + extent,
         ];
         prim.tex_bounds = prim.quad_bounds;
         prim.scissor = self.add_scissor() as u32;
@@ -588,18 +1023,414 @@ impl Vger {
         self.render(prim);
     }
 
-    /// Strokes a quadratic bezier segment.
-    pub fn stroke_bezier<Pt: Into<LocalPoint>>(
+    /// Fills a regular polygon with corners `radius` from the center. The
+    /// first corner is at angle `rotation`. Fewer than 3 sides are drawn as
+    /// a triangle.
+    pub fn fill_polygon<Pt: Into<LocalPoint>>(
         &mut self,
-        a: Pt,
-        b: Pt,
-        c: Pt,
+        center: Pt,
+        radius: f32,
+        sides: u32,
+        rotation: f32,
+        corner_radius: f32,
+        paint_index: PaintIndex,
+    ) {
+        self.polygon_prim(
+            PrimType::Polygon,
+            center.into(),
+            radius,
+            sides.max(3),
+            rotation,
+            corner_radius,
+            0.0,
+            paint_index,
+        );
+    }
+
+    /// Strokes a regular polygon. See `fill_polygon`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn stroke_polygon<Pt: Into<LocalPoint>>(
+        &mut self,
+        center: Pt,
+        radius: f32,
+        sides: u32,
+        rotation: f32,
+        corner_radius: f32,
         width: f32,
         paint_index: PaintIndex,
     ) {
-        let mut prim = Prim::default();
-        prim.prim_type = PrimType::Bezier as u32;
-        let ap: LocalPoint = a.into();
+        self.polygon_prim(
+            PrimType::PolygonStroke,
+            center.into(),
+            radius,
+            sides.max(3),
+            rotation,
+            corner_radius,
+            width,
+            paint_index,
+        );
+    }
+
+    /// Fills a star with tips `outer_radius` from the center and inner
+    /// corners `inner_radius` from the center. The first tip is at angle
+    /// `rotation`. Fewer than 2 points are drawn as 2.
+    pub fn fill_star<Pt: Into<LocalPoint>>(
+        &mut self,
+        center: Pt,
+        outer_radius: f32,
+        inner_radius: f32,
+        points: u32,
+        rotation: f32,
+        paint_index: PaintIndex,
+    ) {
+        self.polygon_prim(
+            PrimType::Star,
+            center.into(),
+            outer_radius,
+            points.max(2),
+            rotation,
+            inner_radius,
+            0.0,
+            paint_index,
+        );
+    }
+
+    /// Strokes a star. See `fill_star`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn stroke_star<Pt: Into<LocalPoint>>(
+        &mut self,
+        center: Pt,
+        outer_radius: f32,
+        inner_radius: f32,
+        points: u32,
+        rotation: f32,
+        width: f32,
+        paint_index: PaintIndex,
+    ) {
+        self.polygon_prim(
+            PrimType::StarStroke,
+            center.into(),
+            outer_radius,
+            points.max(2),
+            rotation,
+            inner_radius,
+            width,
+            paint_index,
+        );
+    }
+
+    /// Strokes a circle. Dashes wrap around the circle.
+    pub fn stroke_circle<Pt: Into<LocalPoint>>(
+        &mut self,
+        center: Pt,
+        radius: f32,
+        width: f32,
+        align: StrokeAlign,
+        dash: Dash,
+        paint_index: PaintIndex,
+    ) {
+        self.stroke_arc(
+            center,
+            radius,
+            width,
+            align,
+            0.0,
+            std::f32::consts::PI,
+            dash,
+            paint_index,
+        );
+    }
+
+    /// Strokes an arc.
+    #[allow(clippy::too_many_arguments)]
+    pub fn stroke_arc<Pt: Into<LocalPoint>>(
+        &mut self,
+        center: Pt,
+        radius: f32,
+        width: f32,
+        align: StrokeAlign,
+        rotation: f32,
+        aperture: f32,
+        dash: Dash,
+        paint_index: PaintIndex,
+    ) {
+        // Strokes too wide for the circle are clamped at its center.
+        let inner = (radius + align.offset(width) - width / 2.0).max(0.0);
+        let outer = radius + align.offset(width) + width / 2.0;
+        let radius = 0.5 * (inner + outer);
+        let width = outer - inner;
+
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::Arc as u32;
+        prim.radius = radius;
+        let c: LocalPoint = center.into();
+        prim.cvs = [
+            c.x,
+            c.y,
+            rotation.sin(),
+            rotation.cos(),
+            aperture.sin(),
+            aperture.cos(),
+        ];
+        prim.width = width;
+        prim.paint = paint_index.index as u32;
+        prim.quad_bounds = [
+            c.x - radius - width,
+            c.y - radius - width,
+            c.x + radius + width,
+            c.y + radius + width,
+        ];
+        prim.tex_bounds = prim.quad_bounds;
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+        self.add_dash(&mut prim, dash);
+        prim.scissor = self.add_scissor() as u32;
+
+        self.render(prim);
+    }
+
+    /// Fills a rectangle.
+    pub fn fill_rect<Rect: Into<LocalRect>, Radii: Into<CornerRadii>>(
+        &mut self,
+        rect: Rect,
+        radius: Radii,
+        paint_index: PaintIndex,
+        blur_radius: f32,
+    ) {
+        let prim = self.rect_prim(rect.into(), radius.into(), paint_index, blur_radius);
+        self.render(prim);
+    }
+
+    fn rect_prim(
+        &mut self,
+        r: LocalRect,
+        radii: CornerRadii,
+        paint_index: PaintIndex,
+        blur_radius: f32,
+    ) -> Prim {
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::Rect as u32;
+        let min = r.min();
+        let max = r.max();
+        prim.cvs[0] = min.x;
+        prim.cvs[1] = min.y;
+        prim.cvs[2] = max.x;
+        prim.cvs[3] = max.y;
+        prim.cvs[4] = blur_radius;
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+        self.add_radii(radii);
+        prim.paint = paint_index.index as u32;
+        prim.quad_bounds = [
+            min.x - blur_radius * 3.0,
+            min.y - blur_radius * 3.0,
+            max.x + blur_radius * 3.0,
+            max.y + blur_radius * 3.0,
+        ];
+        prim.tex_bounds = prim.quad_bounds;
+        prim.scissor = self.add_scissor() as u32;
+        prim
+    }
+
+    /// Draws a CSS style border inside a rectangle. Each side has its own
+    /// width and paint, in the order top, right, bottom, left. Sides meet
+    /// at mitered corners, and the inner corners are rounded by what's
+    /// left of the outer radii.
+    pub fn border_rect<
+        Rect: Into<LocalRect>,
+        Radii: Into<CornerRadii>,
+        Widths: Into<BorderWidths>,
+    >(
+        &mut self,
+        rect: Rect,
+        radius: Radii,
+        widths: Widths,
+        paints: [PaintIndex; 4],
+    ) {
+        let r: LocalRect = rect.into();
+        let radii: CornerRadii = radius.into();
+        let w: BorderWidths = widths.into();
+
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::Border as u32;
+        prim.cvs[0] = r.min_x();
+        prim.cvs[1] = r.min_y();
+        prim.cvs[2] = r.max_x();
+        prim.cvs[3] = r.max_y();
+
+        let inner_min = LocalPoint::new(r.min_x() + w.left, r.min_y() + w.top);
+        let inner_max = LocalPoint::new(r.max_x() - w.right, r.max_y() - w.bottom).max(inner_min);
+        let inner = |r: f32, a: f32, b: f32| (r - a.max(b)).max(0.0);
+
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+        self.add_cv(inner_min);
+        self.add_cv(inner_max);
+        self.add_cv([
+            inner(radii.top_left, w.top, w.left),
+            inner(radii.top_right, w.top, w.right),
+        ]);
+        self.add_cv([
+            inner(radii.bottom_right, w.bottom, w.right),
+            inner(radii.bottom_left, w.bottom, w.left),
+        ]);
+        self.add_radii(radii);
+
+        // The side paints, which shadows leave out. Indices are stored as
+        // bits so they stay exact.
+        let bits = |paint: PaintIndex| f32::from_bits(paint.index as u32);
+        self.add_cv([bits(paints[0]), bits(paints[1])]);
+        self.add_cv([bits(paints[2]), bits(paints[3])]);
+        prim.count = 4;
+
+        prim.paint = paints[0].index as u32;
+
+        prim.quad_bounds = [r.min_x(), r.min_y(), r.max_x(), r.max_y()];
+        prim.tex_bounds = prim.quad_bounds;
+        prim.scissor = self.add_scissor() as u32;
+
+        self.render(prim);
+    }
+
+    /// Draws the shadow of a box, which is not drawn itself. Outer shadows
+    /// extend past the box. Inset shadows are clipped to the inside of the
+    /// box and cast by its edges.
+    pub fn box_shadow<Rect: Into<LocalRect>, Radii: Into<CornerRadii>>(
+        &mut self,
+        rect: Rect,
+        radius: Radii,
+        shadow: BoxShadow,
+        paint_index: PaintIndex,
+    ) {
+        let r: LocalRect = rect.into();
+        let radii: CornerRadii = radius.into();
+
+        // Shrinking past the center would turn the box inside out. Inset
+        // shadows are cast by a box shrunk by the spread.
+        let half_min = 0.5 * r.width().min(r.height());
+        let spread = if shadow.inset {
+            shadow.spread.min(half_min)
+        } else {
+            shadow.spread.max(-half_min)
+        };
+
+        if !shadow.inset {
+            let shadow_rect = r.translate(shadow.offset).inflate(spread, spread);
+            let spread_radii = radii.spread(spread);
+            let prim = self.rect_prim(shadow_rect, spread_radii, paint_index, shadow.blur_radius);
+            self.render_unshadowed(prim);
+            return;
+        }
+
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::InsetShadow as u32;
+
+        // The shadow is cast by the edges of the box, so it's outside a box
+        // shrunk by the spread.
+        let inner = r.translate(shadow.offset).inflate(-spread, -spread);
+        prim.cvs[0] = inner.min_x();
+        prim.cvs[1] = inner.min_y();
+        prim.cvs[2] = inner.max_x();
+        prim.cvs[3] = inner.max_y();
+        prim.cvs[4] = shadow.blur_radius;
+
+        // The box we clip to, then the radii of the inner box.
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+        self.add_cv(r.min());
+        self.add_cv(r.max());
+        self.add_radii(radii);
+        self.add_radii(radii.spread(-spread));
+
+        prim.paint = paint_index.index as u32;
+        prim.quad_bounds = [r.min_x(), r.min_y(), r.max_x(), r.max_y()];
+        prim.tex_bounds = prim.quad_bounds;
+        prim.scissor = self.add_scissor() as u32;
+
+        self.render_unshadowed(prim);
+    }
+
+    /// Strokes a rectangle.
+    #[allow(clippy::too_many_arguments)]
+    pub fn stroke_rect<Radii: Into<CornerRadii>>(
+        &mut self,
+        min: LocalPoint,
+        max: LocalPoint,
+        radius: Radii,
+        width: f32,
+        align: StrokeAlign,
+        dash: Dash,
+        paint_index: PaintIndex,
+    ) {
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::RectStroke as u32;
+        prim.cvs[0] = min.x;
+        prim.cvs[1] = min.y;
+        prim.cvs[2] = max.x;
+        prim.cvs[3] = max.y;
+        prim.width = width;
+        prim.radius = align.offset(width);
+        prim.paint = paint_index.index as u32;
+        let pad = width + prim.radius;
+        prim.quad_bounds = [min.x - pad, min.y - pad, max.x + pad, max.y + pad];
+        prim.tex_bounds = prim.quad_bounds;
+
+        // The dash pattern follows the radii.
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+        self.add_radii(radius.into());
+        self.add_dash(&mut prim, dash);
+        prim.scissor = self.add_scissor() as u32;
+
+        self.render(prim);
+    }
+
+    /// Strokes a line segment.
+    pub fn stroke_segment<Pt: Into<LocalPoint>>(
+        &mut self,
+        a: Pt,
+        b: Pt,
+        width: f32,
+        cap: LineCap,
+        dash: Dash,
+        paint_index: PaintIndex,
+    ) {
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::Segment as u32;
+        let ap: LocalPoint = a.into();
+        let bp: LocalPoint = b.into();
+        prim.cvs[0] = ap.x;
+        prim.cvs[1] = ap.y;
+        prim.cvs[2] = bp.x;
+        prim.cvs[3] = bp.y;
+        prim.width = width;
+        prim.set_cap(cap as u32);
+        prim.paint = paint_index.index as u32;
+        let extent = cap_extent(width, cap);
+        prim.quad_bounds = [
+            ap.x.min(bp.x) - extent,
+            ap.y.min(bp.y) - extent,
+            ap.x.max(bp.x) + extent,
+            ap.y.max(bp.y) + extent,
+        ];
+        prim.tex_bounds = prim.quad_bounds;
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+        self.add_dash(&mut prim, dash);
+        prim.scissor = self.add_scissor() as u32;
+
+        self.render(prim);
+    }
+
+    /// Strokes a quadratic bezier segment.
+    #[allow(clippy::too_many_arguments)]
+    pub fn stroke_bezier<Pt: Into<LocalPoint>>(
+        &mut self,
+        a: Pt,
+        b: Pt,
+        c: Pt,
+        width: f32,
+        cap: LineCap,
+        dash: Dash,
+        paint_index: PaintIndex,
+    ) {
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::Bezier as u32;
+        let ap: LocalPoint = a.into();
         let bp: LocalPoint = b.into();
         let cp: LocalPoint = c.into();
         prim.cvs[0] = ap.x;
@@ -609,12 +1440,151 @@ impl Vger {
         prim.cvs[4] = cp.x;
         prim.cvs[5] = cp.y;
         prim.width = width;
+        prim.set_cap(cap as u32);
         prim.paint = paint_index.index as u32;
+        let extent = cap_extent(width, cap);
         prim.quad_bounds = [
-            ap.x.min(bp.x).min(cp.x) - width,
-            ap.y.min(bp.y).min(cp.y) - width,
-            ap.x.max(bp.x).max(cp.x) + width,
-            ap.y.max(bp.y).max(cp.y) + width,
+            ap.x.min(bp.x).min(cp.x) - extent,
+            ap.y.min(bp.y).min(cp.y) - extent,
+            ap.x.max(bp.x).max(cp.x) + extent,
+            ap.y.max(bp.y).max(cp.y) + extent,
+        ];
+        prim.tex_bounds = prim.quad_bounds;
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+        self.add_dash(&mut prim, dash);
+        prim.scissor = self.add_scissor() as u32;
+
+        self.render(prim);
+    }
+
+    /// Strokes a multi-segment quadratic bezier curve.
+    ///
+    /// Consecutive segments share end points, so `cvs` holds `2n + 1`
+    /// control vertices for `n` segments. Any trailing vertex that doesn't
+    /// complete a segment is ignored.
+    pub fn stroke_curve(&mut self, cvs: &[LocalPoint], width: f32, paint_index: PaintIndex) {
+        if cvs.len() < 3 {
+            return;
+        }
+
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::Curve as u32;
+        prim.width = width;
+        prim.paint = paint_index.index as u32;
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+
+        let mut bounds: Option<LocalRect> = None;
+        let mut i = 0;
+        while i + 2 < cvs.len() {
+            let (a, b, c) = (cvs[i], cvs[i + 1], cvs[i + 2]);
+            self.add_cv(a);
+            self.add_cv(b);
+            self.add_cv(c);
+            prim.count += 1;
+
+            let seg_bounds = bezier_bounds(a, b, c);
+            bounds = Some(bounds.map_or(seg_bounds, |r| union_bounds(&r, &seg_bounds)));
+            i += 2;
+        }
+
+        let bounds = bounds.unwrap().inflate(width, width);
+        prim.quad_bounds = [
+            bounds.min_x(),
+            bounds.min_y(),
+            bounds.max_x(),
+            bounds.max_y(),
+        ];
+        prim.tex_bounds = prim.quad_bounds;
+        prim.scissor = self.add_scissor() as u32;
+
+        self.render(prim);
+    }
+
+    /// Strokes a multi-segment quadratic bezier curve whose width varies
+    /// along its length, for example with pen pressure.
+    ///
+    /// `widths` holds a width for each of the control vertices, which are
+    /// laid out as for `stroke_curve`. Widths are interpolated along each
+    /// segment, and segments join smoothly. Nothing is drawn if there
+    /// isn't one width per control vertex.
+    pub fn stroke_variable_curve(
+        &mut self,
+        cvs: &[LocalPoint],
+        widths: &[f32],
+        paint_index: PaintIndex,
+    ) {
+        if cvs.len() != widths.len() || cvs.len() < 3 {
+            return;
+        }
+
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::VariableCurve as u32;
+        prim.paint = paint_index.index as u32;
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+
+        let mut bounds: Option<LocalRect> = None;
+        let mut max_width: f32 = 0.0;
+        let mut i = 0;
+        while i + 2 < cvs.len() {
+            let (a, b, c) = (cvs[i], cvs[i + 1], cvs[i + 2]);
+            self.add_cv(a);
+            self.add_cv(b);
+            self.add_cv(c);
+            self.add_cv([widths[i], widths[i + 1]]);
+            self.add_cv([widths[i + 2], 0.0]);
+            prim.count += 1;
+
+            let seg_bounds = bezier_bounds(a, b, c);
+            bounds = Some(bounds.map_or(seg_bounds, |r| union_bounds(&r, &seg_bounds)));
+            max_width = max_width
+                .max(widths[i])
+                .max(widths[i + 1])
+                .max(widths[i + 2]);
+            i += 2;
+        }
+
+        let bounds = bounds.unwrap().inflate(max_width, max_width);
+        prim.quad_bounds = [
+            bounds.min_x(),
+            bounds.min_y(),
+            bounds.max_x(),
+            bounds.max_y(),
+        ];
+        prim.tex_bounds = prim.quad_bounds;
+        prim.scissor = self.add_scissor() as u32;
+
+        self.render(prim);
+    }
+
+    /// Strokes a connection wire between two points. The wire sags
+    /// under its own weight like a patch cable.
+    pub fn stroke_wire<Pt: Into<LocalPoint>>(
+        &mut self,
+        a: Pt,
+        b: Pt,
+        width: f32,
+        paint_index: PaintIndex,
+    ) {
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::Wire as u32;
+        let ap: LocalPoint = a.into();
+        let bp: LocalPoint = b.into();
+        prim.cvs[0] = ap.x;
+        prim.cvs[1] = ap.y;
+        prim.cvs[2] = bp.x;
+        prim.cvs[3] = bp.y;
+        prim.radius = WIRE_SAG * (bp - ap).length();
+        prim.width = width;
+        prim.paint = paint_index.index as u32;
+
+        // Must match wireControl in the shader.
+        let control = ap.lerp(bp, 0.5) + LocalVector::new(0.0, 2.0 * prim.radius);
+        let bounds = bezier_bounds(ap, control, bp).inflate(width, width);
+        prim.quad_bounds = [
+            bounds.min_x(),
+            bounds.min_y(),
+            bounds.max_x(),
+            bounds.max_y(),
         ];
         prim.tex_bounds = prim.quad_bounds;
         prim.scissor = self.add_scissor() as u32;
@@ -623,8 +1593,30 @@ impl Vger {
     }
 
     /// Move the pen to a point (path fills only)
+    ///
+    /// This starts a new subpath.
     pub fn move_to<Pt: Into<LocalPoint>>(&mut self, p: Pt) {
+        self.path_scanner.begin_contour();
         self.pen = p.into();
+        self.path_start = self.pen;
+        self.path_open = true;
+    }
+
+    /// Makes a straight line to a point (path fills only)
+    pub fn line_to<Pt: Into<LocalPoint>>(&mut self, b: Pt) {
+        let bp: LocalPoint = b.into();
+        self.path_scanner
+            .segments
+            .push(PathSegment::new(self.pen, self.pen.lerp(bp, 0.5), bp));
+        self.pen = bp;
+        self.path_open = true;
+    }
+
+    /// Closes the current subpath with a straight line back to its
+    /// start (path fills only)
+    pub fn close_path(&mut self) {
+        self.path_scanner.close_contour();
+        self.pen = self.path_start;
     }
 
     /// Makes a quadratic curve to a point (path fills only)
@@ -634,21 +1626,240 @@ impl Vger {
             .segments
             .push(PathSegment::new(self.pen, b.into(), cp));
         self.pen = cp;
+        self.path_open = true;
+    }
+
+    /// Makes a cubic curve to a point (path fills only)
+    ///
+    /// The cubic is approximated by quadratic curves within the path
+    /// tolerance.
+    pub fn cubic_to<Pt: Into<LocalPoint>>(&mut self, b: Pt, c: Pt, d: Pt) {
+        let dp: LocalPoint = d.into();
+        let segments = &mut self.path_scanner.segments;
+        cubic_to_quads(
+            self.pen,
+            b.into(),
+            c.into(),
+            dp,
+            self.path_tolerance,
+            |a, b, c| segments.push(PathSegment::new(a, b, c)),
+        );
+        self.pen = dp;
+        self.path_open = true;
+    }
+
+    /// Adds an elliptical arc to the path (path fills only)
+    ///
+    /// Angles are in radians, and `rotation` turns the ellipse's x axis.
+    /// A straight line connects the pen to the start of the arc. Without a
+    /// current subpath, a new one starts at the start of the arc.
+    pub fn ellipse_arc<Pt: Into<LocalPoint>, Vec: Into<LocalVector>>(
+        &mut self,
+        center: Pt,
+        radii: Vec,
+        rotation: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+    ) {
+        let mut quads = vec![];
+        arc_to_quads(
+            center.into(),
+            radii.into(),
+            rotation,
+            start_angle,
+            sweep_angle,
+            self.path_tolerance,
+            |a, b, c| quads.push([a, b, c]),
+        );
+
+        if let Some(first) = quads.first() {
+            if !self.path_open {
+                self.move_to(first[0]);
+            } else if self.pen != first[0] {
+                self.line_to(first[0]);
+            }
+        }
+
+        for [a, b, c] in quads {
+            self.path_scanner.segments.push(PathSegment::new(a, b, c));
+            self.pen = c;
+        }
+    }
+
+    /// Adds a circular arc to the path (path fills only)
+    ///
+    /// See [`Vger::ellipse_arc`].
+    pub fn arc<Pt: Into<LocalPoint>>(
+        &mut self,
+        center: Pt,
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+    ) {
+        self.ellipse_arc(center, [radius, radius], 0.0, start_angle, sweep_angle);
+    }
+
+    /// Adds an arc of the given radius tangent to the lines from the pen
+    /// to `b` and from `b` to `c`, connecting it to the pen with a
+    /// straight line (path fills only)
+    ///
+    /// Without a current subpath, this just starts one at `b`.
+    pub fn arc_to<Pt: Into<LocalPoint>>(&mut self, b: Pt, c: Pt, radius: f32) {
+        let bp: LocalPoint = b.into();
+        let cp: LocalPoint = c.into();
+
+        if !self.path_open {
+            self.move_to(bp);
+            return;
+        }
+
+        let v0 = (self.pen - bp).normalize();
+        let v1 = (cp - bp).normalize();
+        let cross = v0.cross(v1);
+
+        // Degenerate corners just get a line.
+        if radius <= 0.0 || cross.abs() < 1e-6 || !cross.is_finite() {
+            self.line_to(bp);
+            return;
+        }
+
+        // Distance from the corner to the tangent points.
+        let half_angle = v0.angle_to(v1).radians.abs() / 2.0;
+        let d = radius / half_angle.tan();
+        let center = bp + (v0 + v1).normalize() * (radius / half_angle.sin());
+
+        let start = bp + v0 * d;
+        let end = bp + v1 * d;
+        let start_angle = (start - center).angle_from_x_axis().radians;
+        let sweep = (start - center).angle_to(end - center).radians;
+
+        self.line_to(start);
+        self.arc(center, radius, start_angle, sweep);
+    }
+
+    /// Adds a closed rectangle to the path (path fills only)
+    pub fn add_rect<Rect: Into<LocalRect>>(&mut self, rect: Rect) {
+        let r: LocalRect = rect.into();
+        self.move_to(r.min());
+        self.line_to([r.max_x(), r.min_y()]);
+        self.line_to(r.max());
+        self.line_to([r.min_x(), r.max_y()]);
+        self.close_path();
+    }
+
+    /// Adds a closed rounded rectangle to the path (path fills only)
+    pub fn add_rounded_rect<Rect: Into<LocalRect>>(&mut self, rect: Rect, radius: f32) {
+        let r: LocalRect = rect.into();
+        let radius = radius.min(r.width() / 2.0).min(r.height() / 2.0);
+        if radius <= 0.0 {
+            self.add_rect(r);
+            return;
+        }
+
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let inner = r.inflate(-radius, -radius);
+        self.move_to([r.min_x() + radius, r.min_y()]);
+        self.arc([inner.max_x(), inner.min_y()], radius, -quarter, quarter);
+        self.arc(inner.max(), radius, 0.0, quarter);
+        self.arc([inner.min_x(), inner.max_y()], radius, quarter, quarter);
+        self.arc(inner.min(), radius, 2.0 * quarter, quarter);
+        self.close_path();
+    }
+
+    /// Adds a closed ellipse to the path (path fills only)
+    pub fn add_ellipse<Pt: Into<LocalPoint>, Vec: Into<LocalVector>>(
+        &mut self,
+        center: Pt,
+        radii: Vec,
+    ) {
+        let c: LocalPoint = center.into();
+        let r: LocalVector = radii.into();
+        self.move_to([c.x + r.x, c.y]);
+        self.ellipse_arc(c, r, 0.0, 0.0, 2.0 * std::f32::consts::PI);
+        self.close_path();
+    }
+
+    /// Adds a closed circle to the path (path fills only)
+    pub fn add_circle<Pt: Into<LocalPoint>>(&mut self, center: Pt, radius: f32) {
+        self.add_ellipse(center, [radius, radius]);
+    }
+
+    /// Sets the maximum distance between curves which have to be
+    /// approximated (e.g. cubics) and the quadratic segments rendered
+    /// in their place.
+    pub fn set_path_tolerance(&mut self, tolerance: f32) {
+        self.path_tolerance = tolerance;
+    }
+
+    /// Appends a dash pattern to the control vertices, and sets the number
+    /// of dashes of the prim.
+    fn add_dash(&mut self, prim: &mut Prim, dash: Dash) {
+        if dash.pattern.iter().any(|l| l.is_nan() || *l < 0.0) {
+            return;
+        }
+
+        // Odd patterns are repeated twice to get dash, gap pairs.
+        let len = dash.pattern.len() * (1 + dash.pattern.len() % 2);
+        let length = |i: usize| dash.pattern[i % dash.pattern.len()];
+        let period: f32 = (0..len).map(length).sum();
+        if period <= 0.0 || !period.is_finite() {
+            return;
+        }
+
+        prim.count = (len / 2) as u32;
+        self.add_cv([period, dash.phase.rem_euclid(period)]);
+        let mut offset = 0.0;
+        for i in 0..len / 2 {
+            let on = length(2 * i);
+            let off = length(2 * i + 1);
+            self.add_cv([offset, offset + on]);
+            offset += on + off;
+        }
     }
 
     fn add_cv<Pt: Into<LocalPoint>>(&mut self, p: Pt) {
         self.scenes[self.cur_scene].cvs.push(p.into())
     }
 
+    fn add_radii(&mut self, radii: CornerRadii) {
+        let [top_left, top_right, bottom_right, bottom_left] = radii.to_array();
+        self.add_cv([top_left, top_right]);
+        self.add_cv([bottom_right, bottom_left]);
+    }
+
     /// Fills a path.
-    pub fn fill(&mut self, paint_index: PaintIndex) {
+    ///
+    /// All subpaths are filled together as one shape. The fill rule
+    /// decides whether overlapping contours cut holes.
+    ///
+    /// Paths are split into horizontal bands, each with only the segments
+    /// crossing it. Glowing paths are filled as a single prim, which is
+    /// slower for complex paths.
+    pub fn fill(&mut self, paint_index: PaintIndex, fill_rule: FillRule) {
         let scissor = self.add_scissor();
 
         self.path_scanner.init();
 
+        // Bands can't be blurred past their edges, so glows and shadows
+        // use a single prim with all the segments.
+        if self.glow(paint_index) > 0.0 {
+            if let Some(prim) = self.whole_path_fill(paint_index, fill_rule, scissor) {
+                self.render(prim);
+            }
+            self.path_scanner.clear();
+            self.path_open = false;
+            return;
+        }
+        if self.shadow().is_some() {
+            if let Some(prim) = self.whole_path_fill(paint_index, fill_rule, scissor) {
+                self.render_shadow(&prim);
+            }
+        }
+
         while self.path_scanner.next() {
             let mut prim = Prim::default();
             prim.prim_type = PrimType::PathFill as u32;
+            prim.set_fill_rule(fill_rule as u32);
             prim.paint = paint_index.index as u32;
             prim.scissor = scissor as u32;
             prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
@@ -677,10 +1888,365 @@ impl Vger {
             prim.quad_bounds[3] = self.path_scanner.interval.b;
             prim.tex_bounds = prim.quad_bounds;
 
+            self.render_unshadowed(prim);
+        }
+
+        self.path_scanner.clear();
+        self.path_open = false;
+    }
+
+    /// Fills the whole path with one prim, which is slower than bands
+    /// since every fragment visits every segment.
+    fn whole_path_fill(
+        &mut self,
+        paint_index: PaintIndex,
+        fill_rule: FillRule,
+        scissor: usize,
+    ) -> Option<Prim> {
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::PathFill as u32;
+        prim.set_fill_rule(fill_rule as u32);
+        prim.paint = paint_index.index as u32;
+        prim.scissor = scissor as u32;
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+
+        let mut bounds: Option<LocalRect> = None;
+        for i in 0..self.path_scanner.segments.len() {
+            let [a, b, c] = self.path_scanner.segments[i].cvs;
+            self.add_cv(a);
+            self.add_cv(b);
+            self.add_cv(c);
+            prim.count += 1;
+
+            let seg_bounds = bezier_bounds(a, b, c);
+            bounds = Some(bounds.map_or(seg_bounds, |r| union_bounds(&r, &seg_bounds)));
+        }
+
+        let bounds = bounds?;
+        prim.quad_bounds = [
+            bounds.min_x(),
+            bounds.min_y(),
+            bounds.max_x(),
+            bounds.max_y(),
+        ];
+        prim.tex_bounds = prim.quad_bounds;
+        Some(prim)
+    }
+
+    /// Strokes a path.
+    ///
+    /// All subpaths are rendered as a single primitive, so segments don't
+    /// blend twice where they overlap. Like [`Vger::fill`], this consumes
+    /// the path.
+    pub fn stroke(&mut self, width: f32, style: StrokeStyle, paint_index: PaintIndex) {
+        let scissor = self.add_scissor();
+
+        // Room for caps and joins around the segments.
+        let extent = match style.join {
+            LineJoin::Miter => width.max(width / 2.0 * style.miter_limit),
+            _ => width,
+        };
+
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::PathStroke as u32;
+        prim.width = width;
+        prim.set_cap(style.cap as u32);
+        prim.set_join(style.join as u32);
+        prim.cvs[0] = style.miter_limit;
+        prim.paint = paint_index.index as u32;
+        prim.scissor = scissor as u32;
+        prim.start = self.scenes[self.cur_scene].cvs.len() as u32;
+
+        let mut bounds: Option<LocalRect> = None;
+        for contour in 0..self.path_scanner.contours.len() {
+            let segments = self.path_scanner.contour_segments(contour);
+            if segments.is_empty() {
+                continue;
+            }
+
+            let closed = self.path_scanner.contours[contour].closed;
+            self.add_cv([segments.len() as f32, if closed { 1.0 } else { 0.0 }]);
+            for i in segments {
+                let [a, b, c] = self.path_scanner.segments[i].cvs;
+                self.add_cv(a);
+                self.add_cv(b);
+                self.add_cv(c);
+
+                let seg_bounds = bezier_bounds(a, b, c);
+                bounds = Some(bounds.map_or(seg_bounds, |r| union_bounds(&r, &seg_bounds)));
+            }
+            prim.count += 1;
+        }
+
+        if let Some(bounds) = bounds {
+            let bounds = bounds.inflate(extent, extent);
+            prim.quad_bounds = [
+                bounds.min_x(),
+                bounds.min_y(),
+                bounds.max_x(),
+                bounds.max_y(),
+            ];
+            prim.tex_bounds = prim.quad_bounds;
+
             self.render(prim);
         }
 
-        self.path_scanner.segments.clear();
+        self.path_scanner.clear();
+        self.path_open = false;
+    }
+
+    /// Strokes the line segments between a sequence of points, such as a
+    /// signal trace or plot. Repeated points are skipped.
+    ///
+    /// The points are stored once and the polyline is split into bands
+    /// across its longer side, each with the runs of segments crossing it,
+    /// so long polylines are cheap to draw. Bands meet exactly, so no pixel
+    /// is blended twice. Glowing polylines are drawn as a single prim,
+    /// which is slower for long polylines.
+    pub fn stroke_polyline(
+        &mut self,
+        points: &[LocalPoint],
+        width: f32,
+        style: StrokeStyle,
+        paint_index: PaintIndex,
+    ) {
+        // Room for caps and joins around the segments.
+        let extent = match style.join {
+            LineJoin::Miter => width.max(width / 2.0 * style.miter_limit),
+            _ => width,
+        };
+
+        let mut scanner = std::mem::take(&mut self.polyline_scanner);
+        scanner.begin(points, extent);
+
+        // Repeated points have no direction to join along.
+        if scanner.points.len() >= 2 {
+            self.add_polyline(&mut scanner, width, style, paint_index);
+        }
+        self.polyline_scanner = scanner;
+    }
+
+    fn add_polyline(
+        &mut self,
+        scanner: &mut PolylineScanner,
+        width: f32,
+        style: StrokeStyle,
+        paint_index: PaintIndex,
+    ) {
+        let start = self.scenes[self.cur_scene].cvs.len();
+        for p in &scanner.points {
+            self.add_cv(*p);
+        }
+        let segments = scanner.points.len() - 1;
+
+        let scissor = self.add_scissor();
+
+        // Prim for the runs listed at `runs_start`, covering `bounds`. Each
+        // run is stored as its first point and segment count, followed by
+        // the length of the polyline before it for stroke gradients.
+        // Indices are stored as bits so they stay exact.
+        let bits = |index: usize| f32::from_bits(index as u32);
+        let length = scanner.length;
+        let polyline = |runs_start: usize, runs: usize, bounds: LocalRect| {
+            let mut prim = Prim::default();
+            prim.prim_type = PrimType::Polyline as u32;
+            prim.width = width;
+            prim.set_cap(style.cap as u32);
+            prim.set_join(style.join as u32);
+            prim.cvs[0] = style.miter_limit;
+            prim.cvs[1] = bits(start);
+            prim.cvs[2] = bits(start + segments);
+            prim.cvs[3] = length;
+            prim.paint = paint_index.index as u32;
+            prim.scissor = scissor as u32;
+            prim.start = runs_start as u32;
+            prim.count = runs as u32;
+            prim.quad_bounds = [
+                bounds.min_x(),
+                bounds.min_y(),
+                bounds.max_x(),
+                bounds.max_y(),
+            ];
+            prim.tex_bounds = prim.quad_bounds;
+            prim
+        };
+
+        // Bands can't be blurred past their edges, so glows and shadows
+        // use a single prim with all the segments.
+        let whole = self.scenes[self.cur_scene].cvs.len();
+        if self.glow(paint_index) > 0.0 || self.shadow().is_some() {
+            self.add_cv([bits(start), bits(segments)]);
+            self.add_cv([0.0, 0.0]);
+        }
+        if self.glow(paint_index) > 0.0 {
+            self.render(polyline(whole, 1, scanner.bounds));
+            return;
+        }
+        if self.shadow().is_some() {
+            self.render_shadow(&polyline(whole, 1, scanner.bounds));
+        }
+
+        scanner.scan(POLYLINE_SEGMENTS);
+
+        let runs = &scanner.runs;
+        let mut i = 0;
+        while i < runs.len() {
+            let band = runs[i].band;
+            let runs_start = self.scenes[self.cur_scene].cvs.len();
+            let mut j = i;
+            while j < runs.len() && runs[j].band == band {
+                self.add_cv([bits(start + runs[j].first), bits(runs[j].count)]);
+                self.add_cv([runs[j].before, 0.0]);
+                j += 1;
+            }
+
+            self.render_unshadowed(polyline(runs_start, j - i, scanner.band_bounds(band)));
+            i = j;
+        }
+    }
+
+    /// Fills the area between a series of points and the horizontal line
+    /// at `baseline_y`, as in spectrum and envelope displays. The points
+    /// must be in order of increasing x, or nothing is drawn.
+    ///
+    /// The points are split into vertical strips, and fragments find
+    /// their segment with a binary search, which is much cheaper than a
+    /// path fill.
+    pub fn fill_area(&mut self, points: &[LocalPoint], baseline_y: f32, paint_index: PaintIndex) {
+        // The binary search needs the points in order.
+        if points.len() < 2 || points.windows(2).any(|w| w[0].x > w[1].x) {
+            return;
+        }
+
+        let start = self.scenes[self.cur_scene].cvs.len();
+        for p in points {
+            self.add_cv(*p);
+        }
+
+        let scissor = self.add_scissor();
+        let segments = points.len() - 1;
+        let margin = self.pixel_extent();
+
+        // Prim for the area of `count` segments from `first`.
+        let area = |first: usize, count: usize| {
+            let strip = &points[first..=first + count];
+
+            let mut prim = Prim::default();
+            prim.prim_type = PrimType::Area as u32;
+            prim.cvs[0] = baseline_y;
+            prim.cvs[1] = points[0].x;
+            prim.cvs[2] = points[segments].x;
+            prim.paint = paint_index.index as u32;
+            prim.scissor = scissor as u32;
+            prim.start = (start + first) as u32;
+            prim.count = count as u32;
+
+            // Strips meet exactly so they don't blend twice. Leave a pixel
+            // above and below, and past the ends, for antialiasing.
+            let min_y = strip.iter().fold(baseline_y, |y, p| y.min(p.y));
+            let max_y = strip.iter().fold(baseline_y, |y, p| y.max(p.y));
+            let min_x = if first == 0 { margin.x } else { 0.0 };
+            let max_x = if first + count == segments {
+                margin.x
+            } else {
+                0.0
+            };
+            prim.quad_bounds = [
+                strip[0].x - min_x,
+                min_y - margin.y,
+                strip[count].x + max_x,
+                max_y + margin.y,
+            ];
+            prim.tex_bounds = prim.quad_bounds;
+            prim
+        };
+
+        // Strips can't be blurred past their edges, and the binary search
+        // works for any number of points, so glows and shadows use a
+        // single prim.
+        if self.glow(paint_index) > 0.0 {
+            self.render(area(0, segments));
+            return;
+        }
+        self.render_shadow(&area(0, segments));
+
+        for first in (0..segments).step_by(AREA_SEGMENTS) {
+            let count = AREA_SEGMENTS.min(segments - first);
+            self.render_unshadowed(area(first, count));
+        }
+    }
+
+    /// Fills a rectangle with a grid of lines `spacing` apart, such as
+    /// the background of a canvas. Every `major_every`th line is drawn
+    /// with `major_paint`, or none if zero.
+    ///
+    /// The lines are computed in the shader, in the coordinates of the
+    /// current transform. Lines closer together than a few pixels fade
+    /// out when zoomed out.
+    pub fn fill_grid<Rect: Into<LocalRect>>(
+        &mut self,
+        rect: Rect,
+        spacing: f32,
+        line_width: f32,
+        major_every: u32,
+        minor_paint: PaintIndex,
+        major_paint: PaintIndex,
+    ) {
+        let paints = [minor_paint, major_paint];
+        self.grid(rect.into(), spacing, line_width, major_every, paints, false);
+    }
+
+    /// Fills a rectangle with a grid of dots `spacing` apart. Like
+    /// [`Vger::fill_grid`], every `major_every`th dot in each direction is
+    /// drawn with `major_paint`.
+    pub fn fill_dot_grid<Rect: Into<LocalRect>>(
+        &mut self,
+        rect: Rect,
+        spacing: f32,
+        dot_radius: f32,
+        major_every: u32,
+        minor_paint: PaintIndex,
+        major_paint: PaintIndex,
+    ) {
+        let paints = [minor_paint, major_paint];
+        self.grid(
+            rect.into(),
+            spacing,
+            2.0 * dot_radius,
+            major_every,
+            paints,
+            true,
+        );
+    }
+
+    fn grid(
+        &mut self,
+        r: LocalRect,
+        spacing: f32,
+        width: f32,
+        major_every: u32,
+        paints: [PaintIndex; 2],
+        dots: bool,
+    ) {
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::Grid as u32;
+        prim.cvs[0] = spacing;
+        prim.cvs[1] = major_every as f32;
+
+        // The major paint, which shadows leave out, stored as bits so it
+        // stays exact.
+        prim.cvs[2] = f32::from_bits(paints[1].index as u32);
+        prim.count = 1;
+
+        prim.cvs[3] = if dots { 1.0 } else { 0.0 };
+        prim.width = width;
+        prim.paint = paints[0].index as u32;
+        prim.xform = self.add_xform() as u32;
+        prim.quad_bounds = [r.min_x(), r.min_y(), r.max_x(), r.max_y()];
+        prim.tex_bounds = prim.quad_bounds;
+        prim.scissor = self.add_scissor() as u32;
+
+        self.render(prim);
     }
 
     pub fn render_glyph(
@@ -715,6 +2281,10 @@ impl Vger {
                 (rect.x + rect.width) as f32,
                 (rect.y + rect.height) as f32,
             ];
+
+            // Remember the glyph's region of the atlas for when the quad is
+            // widened for glows and shadows.
+            prim.cvs[..4].copy_from_slice(&prim.tex_bounds);
             prim.paint = paint_index.index as u32;
             prim.scissor = self.add_scissor() as u32;
 
@@ -746,6 +2316,10 @@ impl Vger {
                 (rect.x + rect.width) as f32,
                 (rect.y + rect.height) as f32,
             ];
+
+            // Remember the glyph's region of the atlas for when the quad is
+            // widened for glows and shadows.
+            prim.cvs[..4].copy_from_slice(&prim.tex_bounds);
             prim.scissor = self.add_scissor() as u32;
 
             self.render(prim);
@@ -781,6 +2355,10 @@ impl Vger {
                 (rect.x + rect.width) as f32,
                 (rect.y + rect.height) as f32,
             ];
+
+            // Remember the glyph's region of the atlas for when the quad is
+            // widened for glows and shadows.
+            prim.cvs[..4].copy_from_slice(&prim.tex_bounds);
             if let Some(paint_index) = paint_index {
                 prim.paint = paint_index.index as u32;
             }
@@ -790,6 +2368,57 @@ impl Vger {
         }
     }
 
+    /// Draws a triangle mesh. Each group of three indices is a triangle,
+    /// and colors are interpolated from the vertex colors. Meshes use
+    /// the current transform and scissor, and are ordered with other
+    /// prims by z-index.
+    ///
+    /// Nothing is drawn if there isn't a color for each vertex or an index
+    /// is out of range.
+    pub fn draw_mesh(&mut self, vertices: &[LocalPoint], indices: &[u32], colors: &[Color]) {
+        let valid = vertices.len() == colors.len()
+            && indices.iter().all(|i| (*i as usize) < vertices.len());
+
+        let count = indices.len() - indices.len() % 3;
+        if !valid || count == 0 {
+            return;
+        }
+
+        let xform = self.add_xform() as u32;
+        let scissor = self.add_scissor() as u32;
+
+        let scene = &mut self.scenes[self.cur_scene];
+        let base = scene.mesh_vertices.len() as u32;
+        let start = scene.mesh_indices.len() as u32;
+
+        for (p, color) in vertices.iter().zip(colors) {
+            scene.mesh_vertices.push(MeshVertex {
+                color: *color,
+                position: p.to_array(),
+                xform,
+                scissor,
+            });
+        }
+
+        for index in &indices[..count] {
+            scene.mesh_indices.push(base + index);
+        }
+
+        let mut prim = Prim::default();
+        prim.prim_type = PrimType::Mesh as u32;
+        prim.start = start;
+        prim.count = count as u32;
+        prim.xform = xform;
+        prim.scissor = scissor;
+
+        // Meshes aren't shadowed or glowing, so skip render.
+        scene
+            .depthed_prims
+            .entry(self.cur_z_index)
+            .or_default()
+            .push(prim);
+    }
+
     fn add_xform(&mut self) -> usize {
         if self.xform_count < MAX_PRIMS {
             let m = *self.tx_stack.last().unwrap();
@@ -865,6 +2494,37 @@ impl Vger {
         }
     }
 
+    /// Draws a shadow beneath everything rendered until `reset_shadow`,
+    /// `restore` or the end of the frame. The shadow is offset by `offset`
+    /// and blurred with a gaussian of standard deviation `blur_radius`.
+    /// Box shadows and meshes don't cast shadows.
+    ///
+    /// Blur is exact for rectangles, sampled for glyphs and approximated
+    /// from the distance to the edge for other shapes.
+    pub fn set_shadow<Vec: Into<LocalVector>>(
+        &mut self,
+        offset: Vec,
+        blur_radius: f32,
+        color: Color,
+    ) {
+        let paint = self.color_paint(color);
+
+        if let Some(shadow) = self.shadow_stack.last_mut() {
+            *shadow = Some(Shadow {
+                offset: offset.into(),
+                blur_radius,
+                paint,
+            });
+        }
+    }
+
+    /// Stops drawing shadows.
+    pub fn reset_shadow(&mut self) {
+        if let Some(shadow) = self.shadow_stack.last_mut() {
+            *shadow = None;
+        }
+    }
+
     fn add_paint(&mut self, paint: Paint) -> PaintIndex {
         if self.paint_count < MAX_PRIMS {
             self.scenes[self.cur_scene].paints.push(paint);
@@ -881,6 +2541,18 @@ impl Vger {
         self.add_paint(Paint::solid_color(color))
     }
 
+    /// Gradient along the length of strokes, from `start_color` at the
+    /// start to `end_color` at the end. Works with segments, arcs,
+    /// beziers, wires, curves, variable curves, polylines and path strokes.
+    pub fn stroke_gradient(
+        &mut self,
+        start_color: Color,
+        end_color: Color,
+        glow: f32,
+    ) -> PaintIndex {
+        self.add_paint(Paint::stroke_gradient(start_color, end_color, glow))
+    }
+
     /// Linear gradient paint.
     pub fn linear_gradient<Pt: Into<LocalPoint>>(
         &mut self,
@@ -987,6 +2659,83 @@ impl Vger {
         self.images[image.index] = None;
         self.image_bind_groups[image.index] = None;
     }
+
+    /// Creates a waveform from audio samples in the range -1 to 1. The
+    /// samples are summarized once in a mip pyramid, so drawing at any
+    /// zoom is cheap.
+    pub fn create_waveform(&mut self, samples: &[f32]) -> WaveformIndex {
+        let index = WaveformIndex {
+            index: self.waveforms.len(),
+        };
+        self.waveforms.push(Some(Waveform::new(samples)));
+        index
+    }
+
+    pub fn delete_waveform(&mut self, waveform: WaveformIndex) {
+        self.waveforms[waveform.index] = None;
+    }
+
+    /// Draws the samples in `samples` of a waveform across `rect`, with the
+    /// min/max envelope filled with `envelope_paint` and the RMS envelope
+    /// over it with `rms_paint`. A sample of 1 is at the top of the rect
+    /// and -1 at the bottom. Deleted waveforms draw nothing.
+    pub fn draw_waveform<Rect: Into<LocalRect>>(
+        &mut self,
+        waveform: WaveformIndex,
+        samples: Range<usize>,
+        rect: Rect,
+        envelope_paint: PaintIndex,
+        rms_paint: PaintIndex,
+    ) {
+        let r: LocalRect = rect.into();
+        let w = match &self.waveforms[waveform.index] {
+            Some(w) => w,
+            None => return,
+        };
+        let samples = samples.start..samples.end.min(w.len());
+        if samples.is_empty() || r.width() <= 0.0 {
+            return;
+        }
+
+        // Pick the level with about one entry per pixel.
+        let level = w.level(samples.len() as f32 / (r.width() * self.device_px_ratio));
+        let size = 1 << level;
+        let entries = &w.levels[level];
+
+        // Include a neighbor on each side to interpolate to.
+        let first = (samples.start / size).saturating_sub(1);
+        let last = (samples.end.div_ceil(size) + 1).min(entries.len());
+
+        let start = self.scenes[self.cur_scene].cvs.len() as u32;
+        let cvs = &mut self.scenes[self.cur_scene].cvs;
+        for e in &entries[first..last] {
+            cvs.push([e.min, e.max].into());
+            cvs.push([e.mean_square.sqrt(), 0.0].into());
+        }
+
+        // Positions of the ends of the rect among the entries.
+        let e0 = (samples.start as f64 / size as f64 - first as f64) as f32;
+        let e1 = (samples.end as f64 / size as f64 - first as f64) as f32;
+
+        let scissor = self.add_scissor() as u32;
+
+        for (prim_type, paint) in [
+            (PrimType::Waveform, envelope_paint),
+            (PrimType::WaveformRms, rms_paint),
+        ] {
+            let mut prim = Prim::default();
+            prim.prim_type = prim_type as u32;
+            prim.cvs = [r.min_x(), r.min_y(), r.max_x(), r.max_y(), e0, e1];
+            prim.start = start;
+            prim.count = (last - first) as u32;
+            prim.paint = paint.index as u32;
+            prim.quad_bounds = [r.min_x(), r.min_y(), r.max_x(), r.max_y()];
+            prim.tex_bounds = prim.quad_bounds;
+            prim.scissor = scissor;
+
+            self.render(prim);
+        }
+    }
 }
 
 #[derive(Hash, Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
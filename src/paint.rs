@@ -1,12 +1,18 @@
 use crate::color::*;
 use crate::defs::*;
 
+/// Value of `Paint::image` for gradients along the length of strokes.
+/// Other paints without an image use -1.
+pub const STROKE_GRADIENT_IMAGE: i32 = -2;
+
 #[derive(Clone, Copy)]
 #[repr(C)]
 pub struct Paint {
     xform: WorldToLocal, // mat3x2<f32>
 
-    glow: f32,
+    /// Shapes drawn with the paint glow for this many units past their
+    /// edges.
+    pub glow: f32,
     pub image: i32,
 
     inner_color: Color, // vec4<f32>
@@ -59,6 +65,17 @@ impl Paint {
             glow,
         }
     }
+
+    pub fn stroke_gradient(start_color: Color, end_color: Color, glow: f32) -> Self {
+        // The shader applies the identity to the arc length fraction.
+        Self {
+            xform: WorldToLocal::identity(),
+            inner_color: start_color,
+            outer_color: end_color,
+            image: STROKE_GRADIENT_IMAGE,
+            glow,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -7,6 +7,145 @@ pub struct Interval {
     pub b: f32,
 }
 
+/// Extent of a quadratic bezier along one axis.
+fn bezier_extent(a: f32, b: f32, c: f32) -> Interval {
+    let mut interval = Interval {
+        a: a.min(c),
+        b: a.max(c),
+    };
+
+    // The extremum occurs where the derivative is zero.
+    let d = a - 2.0 * b + c;
+    if d != 0.0 {
+        let t = (a - b) / d;
+        if t > 0.0 && t < 1.0 {
+            let v = (1.0 - t) * (1.0 - t) * a + 2.0 * (1.0 - t) * t * b + t * t * c;
+            interval.a = interval.a.min(v);
+            interval.b = interval.b.max(v);
+        }
+    }
+
+    interval
+}
+
+/// Tight bounding box of a quadratic bezier segment.
+pub fn bezier_bounds(a: LocalPoint, b: LocalPoint, c: LocalPoint) -> LocalRect {
+    let x = bezier_extent(a.x, b.x, c.x);
+    let y = bezier_extent(a.y, b.y, c.y);
+    LocalRect::from_points([LocalPoint::new(x.a, y.a), LocalPoint::new(x.b, y.b)])
+}
+
+/// Smallest rect containing both rects. Unlike `Rect::union`, this keeps
+/// rects with no width or height, such as the bounds of straight segments.
+pub fn union_bounds(a: &LocalRect, b: &LocalRect) -> LocalRect {
+    LocalRect::from_points([a.min(), a.max(), b.min(), b.max()])
+}
+
+/// Default maximum distance between a curve and the quadratic segments
+/// approximating it.
+pub const DEFAULT_TOLERANCE: f32 = 0.1;
+
+/// Upper limit on the number of quadratics used for a single cubic. Since
+/// the error shrinks with the cube of the count, the cap only matters for
+/// cubics whose control points are hundreds of thousands of pixels apart
+/// at the default tolerance; those are drawn with a larger error rather
+/// than an unbounded number of segments.
+const MAX_CUBIC_SUBDIVISIONS: usize = 64;
+
+/// Approximates a cubic bezier with quadratic segments, calling `quad`
+/// for each one. The cubic is split into equal parameter intervals; the
+/// error of each single-quadratic approximation is bounded by
+/// `sqrt(3)/36 * |p3 - 3p2 + 3p1 - p0|` and shrinks with the cube of the
+/// number of intervals, so we can pick the count up front. At most 64
+/// quadratics are emitted, so huge cubics may exceed `tolerance`.
+pub fn cubic_to_quads(
+    p0: LocalPoint,
+    p1: LocalPoint,
+    p2: LocalPoint,
+    p3: LocalPoint,
+    tolerance: f32,
+    mut quad: impl FnMut(LocalPoint, LocalPoint, LocalPoint),
+) {
+    let third_diff = p3.to_vector() - p2.to_vector() * 3.0 + p1.to_vector() * 3.0 - p0.to_vector();
+    let err = third_diff.length() * 3.0f32.sqrt() / 36.0;
+    let n = (err / tolerance.max(f32::EPSILON))
+        .cbrt()
+        .ceil()
+        .clamp(1.0, MAX_CUBIC_SUBDIVISIONS as f32) as usize;
+
+    let point = |t: f32| {
+        let s = 1.0 - t;
+        (p0.to_vector() * (s * s * s)
+            + p1.to_vector() * (3.0 * s * s * t)
+            + p2.to_vector() * (3.0 * s * t * t)
+            + p3.to_vector() * (t * t * t))
+            .to_point()
+    };
+
+    let tangent = |t: f32| {
+        let s = 1.0 - t;
+        (p1 - p0) * (3.0 * s * s) + (p2 - p1) * (6.0 * s * t) + (p3 - p2) * (3.0 * t * t)
+    };
+
+    let dt = 1.0 / n as f32;
+    let mut a = p0;
+    for i in 0..n {
+        let t0 = i as f32 * dt;
+        let t1 = t0 + dt;
+        let d = if i + 1 == n { p3 } else { point(t1) };
+
+        // Control points of the cubic restricted to [t0, t1].
+        let c1 = a + tangent(t0) * (dt / 3.0);
+        let c2 = d - tangent(t1) * (dt / 3.0);
+
+        // Best single quadratic for that cubic.
+        let b = ((c1.to_vector() + c2.to_vector()) * 3.0 - a.to_vector() - d.to_vector()) / 4.0;
+
+        quad(a, b.to_point(), d);
+        a = d;
+    }
+}
+
+/// Approximates an elliptical arc with quadratic segments, calling `quad`
+/// for each one. Angles are in radians, and a positive sweep moves from
+/// the x axis towards the y axis.
+///
+/// A quadratic spanning an angle `theta` of a circle with radius `r` is
+/// off by at most about `r * theta^4 / 100`, which gives us the number of
+/// segments. Quadratics are preserved by affine transforms, so we build
+/// the arc on a circle and stretch it into the ellipse.
+pub fn arc_to_quads(
+    center: LocalPoint,
+    radii: LocalVector,
+    rotation: f32,
+    start_angle: f32,
+    sweep_angle: f32,
+    tolerance: f32,
+    mut quad: impl FnMut(LocalPoint, LocalPoint, LocalPoint),
+) {
+    let radius = radii.x.abs().max(radii.y.abs());
+    let max_angle = (100.0 * tolerance.max(f32::EPSILON) / radius.max(f32::EPSILON))
+        .powf(0.25)
+        .min(std::f32::consts::FRAC_PI_2);
+    let n = (sweep_angle.abs() / max_angle).ceil().max(1.0) as usize;
+
+    let xform = LocalTransform::scale(radii.x, radii.y)
+        .then_rotate(euclid::Angle::radians(rotation))
+        .then_translate(center.to_vector());
+
+    let step = sweep_angle / n as f32;
+    let on_circle = |angle: f32| LocalPoint::new(angle.cos(), angle.sin());
+
+    let mut a = xform.transform_point(on_circle(start_angle));
+    for i in 0..n {
+        let a0 = start_angle + step * i as f32;
+        let b = on_circle(a0 + step / 2.0) / (step / 2.0).cos();
+        let c = xform.transform_point(on_circle(a0 + step));
+        quad(a, xform.transform_point(b), c);
+        a = c;
+    }
+}
+
 pub struct PathSegment {
     pub cvs: [LocalPoint; 3],
     pub next: Option<usize>,
@@ -39,12 +178,25 @@ struct PathScannerNode {
     end: bool,
 }
 
+/// A subpath of a path.
+pub struct Contour {
+    /// Index of the first segment.
+    pub start: usize,
+
+    /// Was the contour explicitly closed?
+    pub closed: bool,
+}
+
 pub struct PathScanner {
     pub segments: Vec<PathSegment>,
     nodes: Vec<PathScannerNode>,
     index: usize,
     pub interval: Interval,
     pub first: Option<usize>,
+
+    /// Contours in order. Each runs until the start of the next one, and
+    /// the last one is the contour currently being built.
+    pub contours: Vec<Contour>,
 }
 
 impl PathScanner {
@@ -55,25 +207,84 @@ impl PathScanner {
             index: 0,
             interval: Interval { a: 0.0, b: 0.0 },
             first: None,
+            contours: vec![Contour {
+                start: 0,
+                closed: false,
+            }],
         }
     }
 
-    pub fn init(&mut self) {
-        // Close the path if necessary.
-        if let Some(first) = self.segments.first() {
-            if let Some(last) = self.segments.last() {
-                let start = first.cvs[0];
-                let end = last.cvs[2];
-                if start != end {
-                    self.segments.push(PathSegment {
-                        cvs: [end, start.lerp(end, 0.5), start],
-                        next: None,
-                        previous: None,
-                    })
-                }
+    /// Removes all segments.
+    pub fn clear(&mut self) {
+        self.segments.clear();
+        self.contours.clear();
+        self.begin_contour();
+    }
+
+    /// Starts a new contour with the following segments.
+    pub fn begin_contour(&mut self) {
+        let start = self.segments.len();
+
+        // Reuse the current contour if it's still empty.
+        if let Some(contour) = self.contours.last_mut() {
+            if contour.start == start {
+                contour.closed = false;
+                return;
             }
         }
 
+        self.contours.push(Contour {
+            start,
+            closed: false,
+        });
+    }
+
+    /// Range of segments in a contour.
+    pub fn contour_segments(&self, i: usize) -> std::ops::Range<usize> {
+        let end = match self.contours.get(i + 1) {
+            Some(next) => next.start,
+            None => self.segments.len(),
+        };
+        self.contours[i].start..end
+    }
+
+    /// Adds a closing line from the end of a contour's segments back to
+    /// its start, if necessary.
+    fn close_segments(&mut self, range: std::ops::Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        let start = self.segments[range.start].cvs[0];
+        let end = self.segments[range.end - 1].cvs[2];
+        if start != end {
+            self.segments.push(PathSegment {
+                cvs: [end, start.lerp(end, 0.5), start],
+                next: None,
+                previous: None,
+            })
+        }
+    }
+
+    /// Closes the current contour and starts a new one.
+    pub fn close_contour(&mut self) {
+        let last = self.contours.len() - 1;
+        self.close_segments(self.contour_segments(last));
+        self.contours[last].closed = true;
+        self.begin_contour();
+    }
+
+    pub fn init(&mut self) {
+        // Fills close all contours. The closing segments are appended
+        // after all the others, which is fine since scanning doesn't care
+        // about the order of segments.
+        let open: Vec<_> = (0..self.contours.len())
+            .filter(|i| !self.contours[*i].closed)
+            .map(|i| self.contour_segments(i))
+            .collect();
+        for range in open {
+            self.close_segments(range);
+        }
+
         self.nodes.clear();
         self.index = 0;
 
@@ -95,7 +306,7 @@ impl PathScanner {
     }
 
     pub fn begin(&mut self, cvs: &[LocalPoint]) {
-        self.segments.clear();
+        self.clear();
 
         let mut i = 0;
         while i < cvs.len() - 2 {
@@ -150,11 +361,423 @@ impl PathScanner {
     }
 }
 
+/// A run of consecutive polyline segments crossing a band.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PolylineRun {
+    pub band: usize,
+
+    /// Index of the first point of the run.
+    pub first: usize,
+
+    /// Number of segments in the run.
+    pub count: usize,
+
+    /// Length of the polyline before the run.
+    pub before: f32,
+}
+
+/// Band of a polyline while it's being split up.
+#[derive(Copy, Clone, Debug)]
+struct PolylineBand {
+    /// Run of segments being added.
+    run: Option<PolylineRun>,
+
+    /// Extent of the segments across the band.
+    min: f32,
+    max: f32,
+}
+
+/// Splits polylines into bands across their longer side, each with the
+/// runs of segments crossing it.
+#[derive(Default)]
+pub struct PolylineScanner {
+    /// Points with repeats removed.
+    pub points: Vec<LocalPoint>,
+
+    /// Length of the polyline.
+    pub length: f32,
+
+    /// Runs of all bands, in order of band.
+    pub runs: Vec<PolylineRun>,
+
+    bands: Vec<PolylineBand>,
+
+    /// Bounds of the polyline, widened by the extent of the stroke.
+    pub bounds: LocalRect,
+    extent: f32,
+
+    /// Do the bands split the polyline along x?
+    vertical: bool,
+    band_size: f32,
+}
+
+impl PolylineScanner {
+    /// Starts a polyline stroked `extent` past its segments.
+    pub fn begin(&mut self, points: &[LocalPoint], extent: f32) {
+        self.points.clear();
+        self.length = 0.0;
+        for p in points {
+            if let Some(last) = self.points.last() {
+                if *p == *last {
+                    continue;
+                }
+                self.length += (*p - *last).length();
+            }
+            self.points.push(*p);
+        }
+
+        self.bounds = LocalRect::from_points(&self.points).inflate(extent, extent);
+        self.extent = extent;
+        self.vertical = self.bounds.width() >= self.bounds.height();
+        self.runs.clear();
+    }
+
+    fn along(&self, p: LocalPoint) -> f32 {
+        if self.vertical {
+            p.x
+        } else {
+            p.y
+        }
+    }
+
+    fn across(&self, p: LocalPoint) -> f32 {
+        if self.vertical {
+            p.y
+        } else {
+            p.x
+        }
+    }
+
+    /// Extent of the bounds along the bands.
+    fn range(&self) -> (f32, f32) {
+        if self.vertical {
+            (self.bounds.min_x(), self.bounds.max_x())
+        } else {
+            (self.bounds.min_y(), self.bounds.max_y())
+        }
+    }
+
+    /// Splits the polyline into bands of about `segments_per_band`
+    /// segments, finding the runs of segments within the extent of each.
+    pub fn scan(&mut self, segments_per_band: usize) {
+        let segments = self.points.len().saturating_sub(1);
+        let band_count = segments.div_ceil(segments_per_band);
+        if band_count == 0 {
+            return;
+        }
+
+        let (lo, hi) = self.range();
+        let band_size = (hi - lo) / band_count as f32;
+        let band_of = |u: f32| (((u - lo) / band_size) as usize).min(band_count - 1);
+        self.band_size = band_size;
+
+        let mut bands = std::mem::take(&mut self.bands);
+        bands.clear();
+        bands.resize(
+            band_count,
+            PolylineBand {
+                run: None,
+                min: f32::MAX,
+                max: f32::MIN,
+            },
+        );
+
+        let mut before = 0.0;
+        for k in 0..segments {
+            let (a, b) = (self.points[k], self.points[k + 1]);
+            let first_band = band_of(self.along(a).min(self.along(b)) - self.extent);
+            let last_band = band_of(self.along(a).max(self.along(b)) + self.extent);
+            for (i, band) in bands[first_band..=last_band].iter_mut().enumerate() {
+                let run = PolylineRun {
+                    band: first_band + i,
+                    first: k,
+                    count: 1,
+                    before,
+                };
+                band.run = match band.run {
+                    Some(r) if r.first + r.count == k => Some(PolylineRun {
+                        count: r.count + 1,
+                        ..r
+                    }),
+                    Some(r) => {
+                        self.runs.push(r);
+                        Some(run)
+                    }
+                    None => Some(run),
+                };
+                band.min = band.min.min(self.across(a).min(self.across(b)));
+                band.max = band.max.max(self.across(a).max(self.across(b)));
+            }
+            before += (b - a).length();
+        }
+        self.runs.extend(bands.iter().filter_map(|band| band.run));
+        self.runs.sort_unstable_by_key(|run| (run.band, run.first));
+
+        self.bands = bands;
+    }
+
+    /// Bounds of a band, widened by the extent of the stroke across it.
+    /// Inner edges of the bands are computed the same way on both sides
+    /// so they meet exactly.
+    pub fn band_bounds(&self, band: usize) -> LocalRect {
+        let (lo, hi) = self.range();
+        let band_lo = if band == 0 {
+            lo
+        } else {
+            lo + band as f32 * self.band_size
+        };
+        let band_hi = if band + 1 == self.bands.len() {
+            hi
+        } else {
+            lo + (band + 1) as f32 * self.band_size
+        };
+        let min = self.bands[band].min - self.extent;
+        let max = self.bands[band].max + self.extent;
+        if self.vertical {
+            LocalRect::from_points([LocalPoint::new(band_lo, min), LocalPoint::new(band_hi, max)])
+        } else {
+            LocalRect::from_points([LocalPoint::new(min, band_lo), LocalPoint::new(max, band_hi)])
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_bezier_bounds() {
+        let r = bezier_bounds([0.0, 0.0].into(), [1.0, 2.0].into(), [2.0, 0.0].into());
+        assert_eq!(r.min(), LocalPoint::new(0.0, 0.0));
+        assert_eq!(r.max(), LocalPoint::new(2.0, 1.0));
+
+        // Control point inside the hull of the end points.
+        let r = bezier_bounds([0.0, 0.0].into(), [1.0, 1.0].into(), [2.0, 2.0].into());
+        assert_eq!(r.min(), LocalPoint::new(0.0, 0.0));
+        assert_eq!(r.max(), LocalPoint::new(2.0, 2.0));
+    }
+
+    #[test]
+    fn test_union_bounds() {
+        // Horizontal and vertical lines have empty bounds.
+        let h = bezier_bounds([0.0, 0.0].into(), [1.0, 0.0].into(), [2.0, 0.0].into());
+        let v = bezier_bounds([2.0, 0.0].into(), [2.0, 1.0].into(), [2.0, 3.0].into());
+        let r = union_bounds(&h, &v);
+        assert_eq!(r.min(), LocalPoint::new(0.0, 0.0));
+        assert_eq!(r.max(), LocalPoint::new(2.0, 3.0));
+    }
+
+    fn cubic_point(cvs: &[LocalPoint; 4], t: f32) -> LocalPoint {
+        let s = 1.0 - t;
+        (cvs[0].to_vector() * (s * s * s)
+            + cvs[1].to_vector() * (3.0 * s * s * t)
+            + cvs[2].to_vector() * (3.0 * s * t * t)
+            + cvs[3].to_vector() * (t * t * t))
+            .to_point()
+    }
+
+    fn quad_point(cvs: &[LocalPoint; 3], t: f32) -> LocalPoint {
+        cvs[0].lerp(cvs[1], t).lerp(cvs[1].lerp(cvs[2], t), t)
+    }
+
+    /// Distance from a point to a densely sampled curve, treating the
+    /// samples as a polyline.
+    fn distance_to_polyline(p: LocalPoint, samples: &[LocalPoint]) -> f32 {
+        samples
+            .windows(2)
+            .map(|s| {
+                let v = s[1] - s[0];
+                let t = ((p - s[0]).dot(v) / v.square_length().max(f32::EPSILON)).clamp(0.0, 1.0);
+                (p - (s[0] + v * t)).length()
+            })
+            .fold(f32::MAX, f32::min)
+    }
+
+    #[test]
+    fn test_cubic_to_quads() {
+        let cubics: [[LocalPoint; 4]; 4] = [
+            // Gentle arch.
+            [
+                [0.0, 0.0].into(),
+                [30.0, 100.0].into(),
+                [70.0, 100.0].into(),
+                [100.0, 0.0].into(),
+            ],
+            // S-curve with an inflection.
+            [
+                [0.0, 0.0].into(),
+                [100.0, 0.0].into(),
+                [0.0, 100.0].into(),
+                [100.0, 100.0].into(),
+            ],
+            // Self-intersecting loop.
+            [
+                [0.0, 0.0].into(),
+                [200.0, 100.0].into(),
+                [-100.0, 100.0].into(),
+                [100.0, 0.0].into(),
+            ],
+            // Degenerate cubic which is really a quadratic.
+            [
+                [0.0, 0.0].into(),
+                [50.0, 50.0].into(),
+                [100.0, 50.0].into(),
+                [150.0, 0.0].into(),
+            ],
+        ];
+
+        for tolerance in [1.0, 0.1, 0.01] {
+            for cubic in &cubics {
+                let mut quads = vec![];
+                cubic_to_quads(
+                    cubic[0],
+                    cubic[1],
+                    cubic[2],
+                    cubic[3],
+                    tolerance,
+                    |a, b, c| quads.push([a, b, c]),
+                );
+
+                assert!(!quads.is_empty());
+                assert_eq!(quads.first().unwrap()[0], cubic[0]);
+                assert_eq!(quads.last().unwrap()[2], cubic[3]);
+                for pair in quads.windows(2) {
+                    assert_eq!(pair[0][2], pair[1][0]);
+                }
+
+                let n = 1000;
+                let cubic_samples: Vec<LocalPoint> = (0..=n)
+                    .map(|i| cubic_point(cubic, i as f32 / n as f32))
+                    .collect();
+
+                let m = 256;
+                let quad_samples: Vec<LocalPoint> = quads
+                    .iter()
+                    .flat_map(|q| (0..m).map(move |i| quad_point(q, i as f32 / m as f32)))
+                    .chain(std::iter::once(cubic[3]))
+                    .collect();
+
+                // Allow a little slack for floating point error.
+                let bound = tolerance * 1.01 + 0.001;
+
+                for p in &quad_samples {
+                    assert!(distance_to_polyline(*p, &cubic_samples) <= bound);
+                }
+                for p in &cubic_samples {
+                    assert!(distance_to_polyline(*p, &quad_samples) <= bound);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_arc_to_quads() {
+        for radius in [1.0, 10.0, 200.0] {
+            for tolerance in [0.5, 0.1, 0.01] {
+                let center = LocalPoint::new(10.0, 20.0);
+                let mut quads = vec![];
+                arc_to_quads(
+                    center,
+                    LocalVector::new(radius, radius),
+                    0.0,
+                    1.0,
+                    -5.0,
+                    tolerance,
+                    |a, b, c| quads.push([a, b, c]),
+                );
+
+                let start = center + LocalVector::new(1.0f32.cos(), 1.0f32.sin()) * radius;
+                let end = center + LocalVector::new((-4.0f32).cos(), (-4.0f32).sin()) * radius;
+                assert!((quads.first().unwrap()[0] - start).length() < 1e-3 * radius);
+                assert!((quads.last().unwrap()[2] - end).length() < 1e-3 * radius);
+
+                for q in &quads {
+                    for i in 0..=100 {
+                        let p = quad_point(q, i as f32 / 100.0);
+                        let err = ((p - center).length() - radius).abs();
+                        assert!(err <= tolerance * 1.01 + 1e-4 * radius);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ellipse_arc_to_quads() {
+        let mut quads = vec![];
+        arc_to_quads(
+            LocalPoint::zero(),
+            LocalVector::new(100.0, 50.0),
+            std::f32::consts::FRAC_PI_2,
+            0.0,
+            std::f32::consts::PI,
+            0.1,
+            |a, b, c| quads.push([a, b, c]),
+        );
+
+        // Rotated a quarter turn, the arc runs from (0, 100) to (0, -100)
+        // through (-50, 0).
+        assert!((quads.first().unwrap()[0] - LocalPoint::new(0.0, 100.0)).length() < 1e-3);
+        assert!((quads.last().unwrap()[2] - LocalPoint::new(0.0, -100.0)).length() < 1e-3);
+
+        for q in &quads {
+            for i in 0..=100 {
+                let p = quad_point(q, i as f32 / 100.0);
+                assert!(p.x <= 1e-3 && p.x >= -50.1);
+
+                // Implicit ellipse equation should be close to one.
+                let e = (p.y / 100.0).powi(2) + (p.x / 50.0).powi(2);
+                assert!((e - 1.0).abs() < 0.01);
+            }
+        }
+    }
+
+    fn add_polyline(scan: &mut PathScanner, points: &[[f32; 2]]) {
+        scan.begin_contour();
+        for i in 0..points.len() - 1 {
+            let a: LocalPoint = points[i].into();
+            let c: LocalPoint = points[i + 1].into();
+            scan.segments.push(PathSegment::new(a, a.lerp(c, 0.5), c));
+        }
+    }
+
+    #[test]
+    fn test_contours() {
+        let mut scan = PathScanner::new();
+
+        // Explicitly closed square with a square hole.
+        add_polyline(&mut scan, &[[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]]);
+        scan.close_contour();
+        add_polyline(&mut scan, &[[1.0, 1.0], [1.0, 3.0], [3.0, 3.0], [3.0, 1.0]]);
+        scan.close_contour();
+
+        // Each contour is closed on its own, without an edge between them.
+        assert_eq!(scan.segments.len(), 8);
+        assert_eq!(scan.contour_segments(0), 0..4);
+        assert_eq!(scan.contour_segments(1), 4..8);
+        assert!(scan.contours[0].closed && scan.contours[1].closed);
+        assert_eq!(scan.segments[3].cvs[0], LocalPoint::new(0.0, 4.0));
+        assert_eq!(scan.segments[3].cvs[2], LocalPoint::new(0.0, 0.0));
+        assert_eq!(scan.segments[7].cvs[0], LocalPoint::new(3.0, 1.0));
+        assert_eq!(scan.segments[7].cvs[2], LocalPoint::new(1.0, 1.0));
+
+        scan.init();
+        assert_eq!(scan.segments.len(), 8);
+
+        // Open contours stay open until they're filled.
+        scan.clear();
+        add_polyline(&mut scan, &[[0.0, 0.0], [4.0, 0.0], [4.0, 4.0]]);
+        add_polyline(&mut scan, &[[5.0, 0.0], [9.0, 0.0], [9.0, 4.0], [5.0, 0.0]]);
+        assert_eq!(scan.segments.len(), 5);
+        assert!(!scan.contours[0].closed && !scan.contours[1].closed);
+
+        scan.init();
+        assert_eq!(scan.segments.len(), 6);
+        assert_eq!(scan.segments[5].cvs[0], LocalPoint::new(4.0, 4.0));
+        assert_eq!(scan.segments[5].cvs[2], LocalPoint::new(0.0, 0.0));
+    }
+
     #[test]
     fn test_path_scanner() {
         let mut scan = PathScanner::new();
@@ -190,4 +813,80 @@ mod tests {
             println!();
         }
     }
+    #[test]
+    fn test_polyline_scanner() {
+        let mut scan = PolylineScanner::default();
+
+        // Repeated points are removed.
+        let points: Vec<LocalPoint> = vec![
+            [0.0, 0.0].into(),
+            [0.0, 0.0].into(),
+            [3.0, 4.0].into(),
+            [3.0, 4.0].into(),
+        ];
+        scan.begin(&points, 1.0);
+        assert_eq!(scan.points, vec![points[0], points[2]]);
+        assert_eq!(scan.length, 5.0);
+
+        let points: Vec<LocalPoint> = (0..=100)
+            .map(|i| LocalPoint::new(4.0 * i as f32, (i % 2) as f32))
+            .collect();
+        scan.begin(&points, 2.0);
+        scan.scan(32);
+
+        // Wider than tall, so the bands split it along x, each with a
+        // single run of all the segments near it.
+        for band in 0..4 {
+            let runs: Vec<_> = scan.runs.iter().filter(|r| r.band == band).collect();
+            assert_eq!(runs.len(), 1);
+
+            let bounds = scan.band_bounds(band);
+            let run = runs[0];
+            for k in 0..100 {
+                let near = points[k].x.max(points[k + 1].x) + 2.0 >= bounds.min_x()
+                    && points[k].x.min(points[k + 1].x) - 2.0 <= bounds.max_x();
+                assert_eq!(near, (run.first..run.first + run.count).contains(&k));
+            }
+            assert!((run.before - run.first as f32 * 17.0f32.sqrt()).abs() < 1e-3);
+            assert_eq!(bounds.min_y(), -2.0);
+            assert_eq!(bounds.max_y(), 3.0);
+        }
+        assert_eq!(scan.runs.len(), 4);
+
+        // Bands meet exactly and cover the polyline.
+        assert_eq!(scan.band_bounds(0).min_x(), scan.bounds.min_x());
+        assert_eq!(scan.band_bounds(3).max_x(), scan.bounds.max_x());
+        for band in 0..3 {
+            assert_eq!(
+                scan.band_bounds(band).max_x(),
+                scan.band_bounds(band + 1).min_x()
+            );
+        }
+
+        // Taller than wide, so the bands split it along y. The polyline
+        // crosses the first band twice.
+        let points: Vec<LocalPoint> = vec![
+            [0.0, 0.0].into(),
+            [0.0, 100.0].into(),
+            [50.0, 100.0].into(),
+            [50.0, 0.0].into(),
+        ];
+        scan.begin(&points, 1.0);
+        scan.scan(1);
+        let runs: Vec<_> = scan
+            .runs
+            .iter()
+            .map(|r| (r.band, r.first, r.count, r.before))
+            .collect();
+        assert_eq!(
+            runs,
+            vec![
+                (0, 0, 1, 0.0),
+                (0, 2, 1, 150.0),
+                (1, 0, 1, 0.0),
+                (1, 2, 1, 150.0),
+                (2, 0, 3, 0.0),
+            ]
+        );
+    }
 }
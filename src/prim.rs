@@ -1,3 +1,5 @@
+use crate::Color;
+
 #[derive(Copy, Clone)]
 #[allow(dead_code)]
 pub enum PrimType {
@@ -36,6 +38,57 @@ pub enum PrimType {
 
     /// Svg with override color
     OverrideColorSvg,
+
+    /// Path strokes.
+    PathStroke,
+
+    /// Filled ellipse.
+    Ellipse,
+
+    /// Stroked ellipse.
+    EllipseStroke,
+
+    /// Filled sector of a ring.
+    Sector,
+
+    /// Filled regular polygon.
+    Polygon,
+
+    /// Stroked regular polygon.
+    PolygonStroke,
+
+    /// Filled star.
+    Star,
+
+    /// Stroked star.
+    StarStroke,
+
+    /// Shadow inside a rectangle.
+    InsetShadow,
+
+    /// Triangle mesh drawn by the mesh pipeline.
+    Mesh,
+
+    /// Rectangle border with a paint for each side.
+    Border,
+
+    /// Multi-segment bezier curve with a width at each control vertex.
+    VariableCurve,
+
+    /// Band of a polyline.
+    Polyline,
+
+    /// Part of the area between a series of points and a baseline.
+    Area,
+
+    /// Min/max envelope of a waveform.
+    Waveform,
+
+    /// RMS envelope of a waveform.
+    WaveformRms,
+
+    /// Grid of minor and major lines or dots.
+    Grid,
 }
 
 #[derive(Copy, Clone, Default)]
@@ -53,7 +106,8 @@ pub struct Prim {
     /// Stroke width.
     pub width: f32,
 
-    /// Radius of circles. Corner radius for rounded rectangles.
+    /// Radius of circles. Sag of wires. Offset of rectangle strokes from
+    /// the edge.
     pub radius: f32,
 
     /// Control vertices.
@@ -77,7 +131,55 @@ pub struct Prim {
     /// Index of scissor.
     pub scissor: u32,
 
-    pad: u32,
+    /// Fill rule, line cap and line join in bits 0-1, 2-3 and 4-5. Blur
+    /// of shadows in 1/16ths of a pixel in bits 16-31.
+    flags: u32,
+}
+
+impl Prim {
+    fn set_flags(&mut self, shift: u32, mask: u32, value: u32) {
+        self.flags = (self.flags & !(mask << shift)) | ((value & mask) << shift);
+    }
+
+    /// Sets the fill rule for path fills.
+    pub fn set_fill_rule(&mut self, fill_rule: u32) {
+        self.set_flags(0, 0x3, fill_rule);
+    }
+
+    /// Sets the line cap for strokes.
+    pub fn set_cap(&mut self, cap: u32) {
+        self.set_flags(2, 0x3, cap);
+    }
+
+    /// Sets the line join for path strokes.
+    pub fn set_join(&mut self, join: u32) {
+        self.set_flags(4, 0x3, join);
+    }
+
+    /// Sets the standard deviation of the gaussian blur applied to
+    /// shadows, which is rounded to 1/16th of a pixel and limited to 4096
+    /// pixels.
+    pub fn set_blur(&mut self, blur: f32) {
+        let blur = (blur * 16.0).round().clamp(0.0, 65535.0) as u32;
+        self.set_flags(16, 0xffff, blur);
+    }
+}
+
+/// Vertex of a triangle mesh.
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct MeshVertex {
+    /// Color interpolated across triangles.
+    pub color: Color,
+
+    /// Position in local space.
+    pub position: [f32; 2],
+
+    /// Index of transform applied to the vertex.
+    pub xform: u32,
+
+    /// Index of scissor.
+    pub scissor: u32,
 }
 
 mod tests {
@@ -86,4 +188,26 @@ mod tests {
     fn test_size() {
         assert_eq!(std::mem::size_of::<super::Prim>(), 96);
     }
+
+    #[test]
+    fn test_flags() {
+        let mut prim = super::Prim::default();
+        prim.set_blur(2.5);
+        prim.set_join(2);
+        prim.set_cap(1);
+        prim.set_fill_rule(1);
+        prim.set_cap(2);
+        assert_eq!(prim.flags, (40 << 16) | (2 << 4) | (2 << 2) | 1);
+
+        // Blur is clamped to its bits without touching the others.
+        prim.set_blur(1e6);
+        assert_eq!(prim.flags, (0xffff << 16) | (2 << 4) | (2 << 2) | 1);
+        prim.set_blur(-1.0);
+        assert_eq!(prim.flags, (2 << 4) | (2 << 2) | 1);
+    }
+
+    #[test]
+    fn test_mesh_vertex_size() {
+        assert_eq!(std::mem::size_of::<super::MeshVertex>(), 32);
+    }
 }
@@ -13,6 +13,8 @@ pub(crate) struct Scene {
     pub xforms: GPUVec<Mat4x4>,
     pub paints: GPUVec<Paint>,
     pub scissors: GPUVec<Scissor>,
+    pub mesh_vertices: GPUVec<MeshVertex>,
+    pub mesh_indices: GPUVec<u32>,
     pub bind_groups: [wgpu::BindGroup; MAX_LAYERS],
 }
 
@@ -34,6 +36,8 @@ impl Scene {
         let xforms = GPUVec::new(device, INIT_PRIMS, "Xform Buffer");
         let paints = GPUVec::new(device, INIT_PRIMS, "Paint Buffer");
         let scissors = GPUVec::new(device, INIT_PRIMS, "scissor Buffer");
+        let mesh_vertices = GPUVec::new(device, INIT_PRIMS, "Mesh Vertex Buffer");
+        let mesh_indices = GPUVec::new(device, INIT_PRIMS, "Mesh Index Buffer");
 
         let bind_group_layout = Self::bind_group_layout(device);
 
@@ -46,6 +50,8 @@ impl Scene {
                 &xforms,
                 &paints,
                 &scissors,
+                &mesh_vertices,
+                &mesh_indices,
             )
         });
 
@@ -56,6 +62,8 @@ impl Scene {
             xforms,
             paints,
             scissors,
+            mesh_vertices,
+            mesh_indices,
             bind_groups,
         }
     }
@@ -68,11 +76,14 @@ impl Scene {
                 GPUVec::<Mat4x4>::bind_group_layout_entry(2),
                 GPUVec::<Paint>::bind_group_layout_entry(3),
                 GPUVec::<Scissor>::bind_group_layout_entry(4),
+                GPUVec::<MeshVertex>::bind_group_layout_entry(5),
+                GPUVec::<u32>::bind_group_layout_entry(6),
             ],
             label: Some("BindGroupLayout for Scene"),
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn bind_group(
         device: &wgpu::Device,
         bind_group_layout: &wgpu::BindGroupLayout,
@@ -81,6 +92,8 @@ impl Scene {
         xforms: &GPUVec<Mat4x4>,
         paints: &GPUVec<Paint>,
         scissors: &GPUVec<Scissor>,
+        mesh_vertices: &GPUVec<MeshVertex>,
+        mesh_indices: &GPUVec<u32>,
     ) -> wgpu::BindGroup {
         device.create_bind_group(&wgpu::BindGroupDescriptor {
             layout: bind_group_layout,
@@ -90,6 +103,8 @@ impl Scene {
                 xforms.bind_group_entry(2),
                 paints.bind_group_entry(3),
                 scissors.bind_group_entry(4),
+                mesh_vertices.bind_group_entry(5),
+                mesh_indices.bind_group_entry(6),
             ],
             label: Some("vger bind group"),
         })
@@ -112,6 +127,8 @@ impl Scene {
         update_bind_groups |= self.xforms.update(device, queue);
         update_bind_groups |= self.paints.update(device, queue);
         update_bind_groups |= self.scissors.update(device, queue);
+        update_bind_groups |= self.mesh_vertices.update(device, queue);
+        update_bind_groups |= self.mesh_indices.update(device, queue);
 
         // If anything changed, regenerate all the bind groups.
         if update_bind_groups {
@@ -125,6 +142,8 @@ impl Scene {
                     &self.xforms,
                     &self.paints,
                     &self.scissors,
+                    &self.mesh_vertices,
+                    &self.mesh_indices,
                 );
             }
         }
@@ -139,5 +158,7 @@ impl Scene {
         self.xforms.clear();
         self.paints.clear();
         self.scissors.clear();
+        self.mesh_vertices.clear();
+        self.mesh_indices.clear();
     }
 }
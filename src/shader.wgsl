@@ -34,6 +34,65 @@ const vgerPathFill = 10;
 /// Svg with override color
 const overrideColorSvg = 11;
 
+/// Path strokes.
+const vgerPathStroke = 12;
+
+/// Filled ellipse.
+const vgerEllipse = 13;
+
+/// Stroked ellipse.
+const vgerEllipseStroke = 14;
+
+/// Filled sector of a ring.
+const vgerSector = 15;
+
+/// Regular polygons.
+const vgerPolygon = 16;
+const vgerPolygonStroke = 17;
+
+/// Stars.
+const vgerStar = 18;
+const vgerStarStroke = 19;
+
+/// Shadow inside a rectangle.
+const vgerInsetShadow = 20;
+
+/// Triangle mesh. Drawn with vs_mesh and fs_mesh instead.
+const vgerMesh = 21;
+
+/// Rectangle border with a paint for each side.
+const vgerBorder = 22;
+
+/// Multi-segment bezier curve with a width at each control vertex.
+const vgerVariableCurve = 23;
+
+/// Polyline of line segments.
+const vgerPolyline = 24;
+
+/// Area between a series of points and a baseline.
+const vgerArea = 25;
+
+/// Min/max and RMS envelopes of a waveform.
+const vgerWaveform = 26;
+const vgerWaveformRms = 27;
+
+/// Grid of minor and major lines or dots.
+const vgerGrid = 28;
+
+/// Path fill rules.
+const vgerNonZero = 0u;
+const vgerEvenOdd = 1u;
+
+/// Line caps.
+const vgerButtCap = 0u;
+const vgerRoundCap = 1u;
+const vgerSquareCap = 2u;
+
+/// Line joins.
+const vgerMiterJoin = 0u;
+const vgerRoundJoin = 1u;
+const vgerBevelJoin = 2u;
+
 struct Prim {
 
     /// Min and max coordinates of the quad we're rendering.
@@ -49,7 +108,8 @@ struct Prim {
     /// Stroke width.
     width: f32,
 
-    /// Radius of circles. Corner radius for rounded rectangles.
+    /// Radius of circles. Sag of wires. Offset of rectangle strokes from
+    /// the edge.
     radius: f32,
 
     /// Control vertices.
@@ -76,11 +136,38 @@ struct Prim {
     /// Index of scissor rectangle.
     scissor: u32,
 
-    /// Alignment padding.
-    pad: u32,
+    /// Fill rule, line cap and line join in bits 0-1, 2-3 and 4-5. Blur
+    /// of shadows in 1/16ths of a pixel in bits 16-31.
+    flags: u32,
 
 };
 
+/// Fill rule of path fills.
+fn fillRule(prim: Prim) -> u32 {
+    return prim.flags & 3u;
+}
+
+/// Line cap of strokes.
+fn lineCap(prim: Prim) -> u32 {
+    return (prim.flags >> 2u) & 3u;
+}
+
+/// Line join of path strokes.
+fn lineJoin(prim: Prim) -> u32 {
+    return (prim.flags >> 4u) & 3u;
+}
+
+/// Standard deviation of the gaussian blur applied to shadows.
+fn shadowBlur(prim: Prim) -> f32 {
+    return f32(prim.flags >> 16u) / 16.0;
+}
+
+/// Corner radii of rectangles stored at cvs[j] and cvs[j+1]: top left,
+/// top right, bottom right and bottom left.
+fn cornerRadii(j: u32) -> vec4<f32> {
+    return vec4<f32>(cvs.cvs[j], cvs.cvs[j+1u]);
+}
+
 fn proj(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
     return normalize(a) * dot(a,b) / length(a);
 }
@@ -101,12 +188,109 @@ fn sdCircle(p: vec2<f32>, r: f32) -> f32
     return length(p) - r;
 }
 
+// From https://iquilezles.org/articles/ellipsedist
+fn sdEllipse(p: vec2<f32>, radii: vec2<f32>) -> f32
+{
+    var pp = abs(p);
+    var ab = radii;
+    if(abs(ab.x - ab.y) <= 1e-4 * max(ab.x, ab.y)) {
+        return length(pp) - ab.x;
+    }
+    if(pp.x > pp.y) {
+        pp = pp.yx;
+        ab = ab.yx;
+    }
+    let l = ab.y*ab.y - ab.x*ab.x;
+    let m = ab.x*pp.x/l;
+    let m2 = m*m;
+    let n = ab.y*pp.y/l;
+    let n2 = n*n;
+    let c = (m2 + n2 - 1.0)/3.0;
+    let c3 = c*c*c;
+    let q = c3 + m2*n2*2.0;
+    let d = c3 + m2*n2;
+    let g = m + m*n2;
+    var co = 0.0;
+    if(d < 0.0) {
+        let h = acos(clamp(q/c3, -1.0, 1.0))/3.0;
+        let s = cos(h);
+        let t = sin(h)*1.732050808;
+        let rx = sqrt(-c*(s + t + 2.0) + m2);
+        let ry = sqrt(-c*(s - t + 2.0) + m2);
+        co = (ry + sign(l)*rx + abs(g)/(rx*ry) - m)/2.0;
+    } else {
+        let h = 2.0*m*n*sqrt(d);
+        let s = sign(q+h)*pow(abs(q+h), 1.0/3.0);
+        let u = sign(q-h)*pow(abs(q-h), 1.0/3.0);
+        let rx = -s - u - c*4.0 + 2.0*m2;
+        let ry = (s - u)*1.732050808;
+        let rm = sqrt(rx*rx + ry*ry);
+        co = (ry/sqrt(rm-rx) + 2.0*g/rm - m)/2.0;
+    }
+    let r = ab * vec2<f32>(co, sqrt(max(1.0-co*co, 0.0)));
+    return length(r-pp) * sign(pp.y-r.y);
+}
+
 fn sdBox(p: vec2<f32>, b: vec2<f32>, r: f32) -> f32
 {
     let d = abs(p)-b+r;
     return length(max(d,vec2<f32>(0.0, 0.0))) + min(max(d.x,d.y),0.0)-r;
 }
 
+/// Radius of the corner of a rectangle in the quadrant of p.
+fn cornerRadius(p: vec2<f32>, radii: vec4<f32>) -> f32
+{
+    let r = select(radii.wz, radii.xy, p.y < 0.0);
+    return select(r.y, r.x, p.x < 0.0);
+}
+
+/// Box with a different radius for each corner.
+fn sdRoundedBox(p: vec2<f32>, b: vec2<f32>, radii: vec4<f32>) -> f32
+{
+    return sdBox(p, b, cornerRadius(p, radii));
+}
+
+/// Reduces p to the first sector of a shape with n-fold symmetry, with
+/// corners at angle `rotation` and the sector's corner at angle pi/n.
+fn polygonSector(p: vec2<f32>, n: f32, rotation: f32) -> vec2<f32>
+{
+    let an = 3.141592653589793/n;
+    let a = atan2(p.y, p.x) - rotation;
+    let bn = a - 2.0*an*floor(a/(2.0*an)) - an;
+    return length(p)*vec2<f32>(cos(bn), abs(sin(bn)));
+}
+
+// From https://iquilezles.org/articles/distfunctions2d
+fn sdRegularPolygon(p: vec2<f32>, r: f32, n: f32, rotation: f32) -> f32
+{
+    let an = 3.141592653589793/n;
+    let acs = vec2<f32>(cos(an), sin(an));
+    var q = polygonSector(p, n, rotation) - r*acs;
+    q.y = q.y + clamp(-q.y, 0.0, r*acs.y);
+    return length(q)*sign(q.x);
+}
+
+/// Regular polygon with corners rounded by `corner`.
+fn sdRoundedPolygon(p: vec2<f32>, r: f32, n: f32, rotation: f32, corner: f32) -> f32
+{
+    let an = 3.141592653589793/n;
+    return sdRegularPolygon(p, r - corner/cos(an), n, rotation) - corner;
+}
+
+// From https://iquilezles.org/articles/distfunctions2d
+fn sdStar(p: vec2<f32>, r: f32, inner: f32, n: f32, rotation: f32) -> f32
+{
+    let an = 3.141592653589793/n;
+    let acs = vec2<f32>(cos(an), sin(an));
+
+    // Direction of the edge from the inner corner to the point.
+    let ecs = normalize(r*acs - vec2<f32>(inner, 0.0));
+
+    var q = polygonSector(p, n, rotation) - r*acs;
+    q = q + ecs*clamp(-dot(q, ecs), 0.0, r*acs.y/ecs.y);
+    return length(q)*sign(q.x);
+}
+
 fn sdSegment(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>, width: f32) -> f32
 {
     var dir = a-b;
@@ -184,6 +368,17 @@ fn sdBezier(pos: vec2<f32>, A: vec2<f32>, B: vec2<f32>, C: vec2<f32> ) -> f32
     return sqrt( res );
 }
 
+/// Distance to a path segment. Straight lines are stored with their
+/// control point in the middle, which sdBezier can't handle.
+fn sdPathSegment(p: vec2<f32>, A: vec2<f32>, B: vec2<f32>, C: vec2<f32>) -> f32
+{
+    let b = A - 2.0*B + C;
+    if(dot(b, b) <= 1e-8 * dot2(C - A)) {
+        return length(closestPointInSegment(A - p, C - p));
+    }
+    return sdBezier(p, A, B, C);
+}
+
 fn sdSubtract(d1: f32, d2: f32) -> f32
 {
     return max(-d1, d2);
@@ -271,6 +466,691 @@ fn sdBezierApprox2(p: vec2<f32>, A: vec2<f32>, B: vec2<f32>, C: vec2<f32>) -> f3
     return length(get_distance_vector(A-p, B-p, C-p));
 }
 
+/// Control point of the parabola a sagging wire hangs along. The
+/// midpoint of the wire sits `sag` below the line between its ends.
+fn wireControl(a: vec2<f32>, b: vec2<f32>, sag: f32) -> vec2<f32> {
+    return 0.5*(a + b) + vec2<f32>(0.0, 2.0*sag);
+}
+
+/// Connection wire. A hanging cable is approximated by a parabola, which
+/// is exactly a quadratic bezier, so we can use the exact bezier distance.
+fn sdWire(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>, sag: f32) -> f32 {
+    if(sag <= 0.0) {
+        return length(closestPointInSegment(a - p, b - p));
+    }
+    return sdBezier(p, a, wireControl(a, b, sag), b);
+}
+
+/// Tangent at the start of a quadratic segment.
+fn startTangent(a: vec2<f32>, b: vec2<f32>, c: vec2<f32>) -> vec2<f32> {
+    return normalize(select(b - a, c - a, all(a == b)));
+}
+
+/// Tangent at the end of a quadratic segment.
+fn endTangent(a: vec2<f32>, b: vec2<f32>, c: vec2<f32>) -> vec2<f32> {
+    return normalize(select(c - b, c - a, all(b == c)));
+}
+
+/// Distance to the region a butt cap cuts from a round end at `e`, where
+/// `dir` points outward.
+fn buttCut(p: vec2<f32>, e: vec2<f32>, dir: vec2<f32>, hw: f32) -> f32 {
+    return max(-dot(p - e, dir), length(p - e) - 2.0*hw);
+}
+
+/// Distance to a square cap at `e`, where `dir` points outward.
+fn sdSquareCap(p: vec2<f32>, e: vec2<f32>, dir: vec2<f32>, hw: f32) -> f32 {
+    let q = vec2<f32>(dot(p - e, dir), dot(p - e, rot90(dir)));
+    return sdBox(q, vec2<f32>(hw), 0.0);
+}
+
+/// Applies line caps to the distance `d` to a round ended stroke with
+/// ends `e0` and `e1`. `dir0` and `dir1` point outward from the ends.
+fn sdCaps(p: vec2<f32>, d: f32, e0: vec2<f32>, dir0: vec2<f32>, e1: vec2<f32>, dir1: vec2<f32>, hw: f32, cap: u32) -> f32 {
+    if(cap == vgerSquareCap) {
+        return min(d, min(sdSquareCap(p, e0, dir0, hw), sdSquareCap(p, e1, dir1, hw)));
+    }
+    if(cap == vgerButtCap) {
+        return max(d, -min(buttCut(p, e0, dir0, hw), buttCut(p, e1, dir1, hw)));
+    }
+    return d;
+}
+
+/// Parameter of the point on a quadratic bezier closest to `pos`.
+fn bezierClosestT(pos: vec2<f32>, A: vec2<f32>, B: vec2<f32>, C: vec2<f32>) -> f32
+{
+    let a = B - A;
+    let b = A - 2.0*B + C;
+    let c = a * 2.0;
+    let d = A - pos;
+    if(dot(b, b) <= 1e-8 * dot2(C - A)) {
+        return clamp(dot(pos - A, C - A) / dot2(C - A), 0.0, 1.0);
+    }
+    let kk = 1.0/dot(b,b);
+    let kx = kk * dot(a,b);
+    let ky = kk * (2.0*dot(a,a)+dot(d,b)) / 3.0;
+    let kz = kk * dot(d,a);
+    let p = ky - kx*kx;
+    let p3 = p*p*p;
+    let q = kx*(2.0*kx*kx + -3.0*ky) + kz;
+    var h = q*q + 4.0*p3;
+    if( h >= 0.0)
+    {
+        h = sqrt(h);
+        let x = (vec2<f32>(h,-h)-q)/2.0;
+        let uv = sign(x)*pow(abs(x), vec2<f32>(1.0/3.0));
+        return clamp( uv.x+uv.y-kx, 0.0, 1.0 );
+    }
+    let z = sqrt(-p);
+    let v = acos( q/(p*z*2.0) ) / 3.0;
+    let m = cos(v);
+    let n = sin(v)*1.732050808;
+    let t = clamp(vec3<f32>(m+m,-n-m,n-m)*z-kx, vec3<f32>(0.0), vec3<f32>(1.0));
+    return select(t.y, t.x, dot2(d+(c+b*t.x)*t.x) < dot2(d+(c+b*t.y)*t.y));
+}
+
+/// Length of a quadratic bezier from its start to parameter `t`.
+fn bezierLength(A: vec2<f32>, B: vec2<f32>, C: vec2<f32>, t: f32) -> f32
+{
+    let a = B - A;
+    let b = A - 2.0*B + C;
+
+    // The speed is 2|a + b t|, so integrate sqrt(k2 t^2 + k1 t + k0).
+    let k2 = dot(b, b);
+    let k1 = 2.0*dot(a, b);
+    let k0 = dot(a, a);
+    if(k2 <= 1e-8 * k0) {
+        return 2.0*sqrt(k0)*t;
+    }
+    let sk2 = sqrt(k2);
+    let c = (4.0*k2*k0 - k1*k1) / (8.0*k2*sk2);
+    let q0 = sqrt(k0);
+    let q1 = sqrt(max(k2*t*t + k1*t + k0, 0.0));
+    let f0 = k1*q0/(4.0*k2) + c*log(max(2.0*sk2*q0 + k1, 1e-10));
+    let f1 = (2.0*k2*t + k1)*q1/(4.0*k2) + c*log(max(2.0*sk2*q1 + 2.0*k2*t + k1, 1e-10));
+    return 2.0*(f1 - f0);
+}
+
+/// Fraction of the length of a quadratic bezier at the point closest to `p`.
+fn bezierFraction(p: vec2<f32>, A: vec2<f32>, B: vec2<f32>, C: vec2<f32>) -> f32 {
+    let len = bezierLength(A, B, C, 1.0);
+    if(len <= 0.0) {
+        return 0.0;
+    }
+    return bezierLength(A, B, C, bezierClosestT(p, A, B, C)) / len;
+}
+
+/// Segment of a multi-segment bezier curve closest to a point, with the
+/// lengths of the curve before it and in total.
+struct CurveSegment {
+    d: f32,
+    j: i32,
+    before: f32,
+    len: f32,
+};
+
+fn noCurveSegment() -> CurveSegment {
+    return CurveSegment(1e10, 0, 0.0, 0.0);
+}
+
+/// Continues the search for the segment closest to `p` with the `n`
+/// segments whose control vertices start at `j0`, `stride` cvs apart.
+fn closestCurveSegment(closest: CurveSegment, p: vec2<f32>, j0: i32, n: i32, stride: i32) -> CurveSegment {
+    var c = closest;
+    for(var i=0; i<n; i = i+1) {
+        let j = j0 + stride*i;
+        let di = sdBezierApprox(p, cvs.cvs[j], cvs.cvs[j+1], cvs.cvs[j+2]);
+        if(di < c.d) {
+            c.d = di;
+            c.j = j;
+            c.before = c.len;
+        }
+        c.len += bezierLength(cvs.cvs[j], cvs.cvs[j+1], cvs.cvs[j+2], 1.0);
+    }
+    return c;
+}
+
+/// Fraction of the length of a multi-segment curve at the point closest
+/// to `p` on its closest segment.
+fn curveFraction(c: CurveSegment, p: vec2<f32>) -> f32 {
+    if(c.len <= 0.0) {
+        return 0.0;
+    }
+    let A = cvs.cvs[c.j];
+    let B = cvs.cvs[c.j+1];
+    let C = cvs.cvs[c.j+2];
+    return (c.before + bezierLength(A, B, C, bezierClosestT(p, A, B, C))) / c.len;
+}
+
+/// Fraction of the length of a stroke at the point closest to `p`, for
+/// gradients along strokes. Path strokes run through their contours in
+/// order.
+fn strokeFraction(prim: Prim, p: vec2<f32>) -> f32 {
+    switch(prim.prim_type) {
+        case 1u: { // vgerArc
+            let pp = (p - prim.cv0) * mat2x2<f32>(prim.cv1, vec2<f32>(-prim.cv1.y, prim.cv1.x));
+            let aperture = atan2(prim.cv2.x, prim.cv2.y);
+            if(aperture <= 0.0) {
+                return 0.0;
+            }
+            let theta = clamp(atan2(pp.x, pp.y), -aperture, aperture);
+            return (theta + aperture) / (2.0*aperture);
+        }
+        case 4u: { // vgerBezier
+            return bezierFraction(p, prim.cv0, prim.cv1, prim.cv2);
+        }
+        case 5u: { // vgerSegment
+            let ba = prim.cv1 - prim.cv0;
+            return clamp(dot(p - prim.cv0, ba) / max(dot2(ba), 1e-10), 0.0, 1.0);
+        }
+        case 6u: { // vgerCurve
+            return curveFraction(closestCurveSegment(noCurveSegment(), p, i32(prim.start), i32(prim.count), 3), p);
+        }
+        case 7u: { // vgerWire
+            return bezierFraction(p, prim.cv0, wireControl(prim.cv0, prim.cv1, prim.radius), prim.cv1);
+        }
+        case 12u: { // vgerPathStroke
+            var c = noCurveSegment();
+            var j0 = i32(prim.start);
+            for(var contour = 0u; contour < prim.count; contour = contour + 1u) {
+                let n = i32(cvs.cvs[j0].x);
+                c = closestCurveSegment(c, p, j0 + 1, n, 3);
+                j0 = j0 + 1 + 3*n;
+            }
+            return curveFraction(c, p);
+        }
+        case 23u: { // vgerVariableCurve
+            return curveFraction(closestCurveSegment(noCurveSegment(), p, i32(prim.start), i32(prim.count), 5), p);
+        }
+        case 24u: { // vgerPolyline
+            // Find the closest segment in the band and the length before it.
+            var d = 1e10;
+            var s = 0.0;
+            for(var r = 0u; r < prim.count; r = r + 1u) {
+                let run = vec2<i32>(bitcast<vec2<u32>>(cvs.cvs[prim.start + 2u*r]));
+                var before = cvs.cvs[prim.start + 2u*r + 1u].x;
+                for(var i = run.x; i < run.x + run.y; i = i+1) {
+                    let a = cvs.cvs[i];
+                    let ba = cvs.cvs[i+1] - a;
+                    let h = clamp(dot(p - a, ba) / max(dot2(ba), 1e-10), 0.0, 1.0);
+                    let di = length(p - a - ba*h);
+                    if(di < d) {
+                        d = di;
+                        s = before + h*length(ba);
+                    }
+                    before += length(ba);
+                }
+            }
+            return s / max(prim.cv1.y, 1e-10);
+        }
+        default: { }
+    }
+    return 0.0;
+}
+
+/// Multi-segment bezier curve with a width at each control vertex.
+///
+/// Each segment is stored at prim.start as its three control vertices
+/// followed by their widths, packed as (w0, w1) and (w2, 0). Widths are
+/// interpolated like the curve, and segments are unioned with round ends
+/// so they join smoothly.
+fn sdVariableCurve(prim: Prim, p: vec2<f32>) -> f32 {
+    var d = 1e10;
+    for(var i=0; i<i32(prim.count); i = i+1) {
+        let j = i32(prim.start) + 5*i;
+        let A = cvs.cvs[j];
+        let B = cvs.cvs[j+1];
+        let C = cvs.cvs[j+2];
+        let w = vec3<f32>(cvs.cvs[j+3], cvs.cvs[j+4].x);
+
+        let t = bezierClosestT(p, A, B, C);
+        let a = B - A;
+        let b = A - 2.0*B + C;
+        let q = A + (2.0*a + b*t)*t;
+        let s = 1.0 - t;
+        let width = dot(w, vec3<f32>(s*s, 2.0*s*t, t*t));
+        d = min(d, length(p - q) - 0.5*width);
+    }
+    return d;
+}
+
+/// Distance along a stroke to the nearest dash, negative inside the dash.
+///
+/// The dash pattern is stored at prim.start: the period and phase,
+/// followed by prim.count dashes as start and end offsets into the period.
+/// `s` is the arc length of the point and `len` the length of the stroke.
+/// Dashes of open strokes are clipped to the stroke.
+fn dashDistance(prim: Prim, j: u32, s: f32, len: f32, closed: bool) -> f32 {
+    let pattern = cvs.cvs[j];
+    let period = pattern.x;
+
+    // Start of the period around the point.
+    let sc = select(clamp(s, 0.0, len), s, closed);
+    let u = sc + pattern.y;
+    let base = sc - (u - period*floor(u/period));
+
+    var da = 1e10;
+    for(var i=1u; i<=prim.count; i = i+1u) {
+        let dash = cvs.cvs[j + i];
+        for(var k=-1.0; k<=1.0; k = k+1.0) {
+            var s0 = base + dash.x + k*period;
+            var s1 = base + dash.y + k*period;
+            if(!closed) {
+                s0 = max(s0, 0.0);
+                s1 = min(s1, len);
+            }
+            if(s0 <= s1) {
+                da = min(da, max(s0 - s, s - s1));
+            }
+        }
+    }
+    return da;
+}
+
+/// Distance to a dash, given the distance along the stroke from
+/// dashDistance and the distance across the stroke.
+fn sdDash(da: f32, perp: f32, hw: f32, cap: u32) -> f32 {
+    if(cap == vgerRoundCap) {
+        return length(vec2<f32>(max(da, 0.0), perp)) - hw;
+    }
+    let q = vec2<f32>(select(da, da - hw, cap == vgerSquareCap), perp - hw);
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0);
+}
+
+/// Distance to a dashed stroke given the nearest point on the center line,
+/// its tangent, and its arc length.
+fn sdDashedStroke(prim: Prim, p: vec2<f32>, c: vec2<f32>, tangent: vec2<f32>, s: f32, len: f32, closed: bool) -> f32 {
+    let q = p - c;
+    let da = dashDistance(prim, prim.start, s + dot(q, tangent), len, closed);
+    return sdDash(da, abs(det(tangent, q)), prim.width/2.0, lineCap(prim));
+}
+
+/// Arc length around the center line of a rounded rectangle to the point
+/// closest to `p`, starting at the left end of the top side and going
+/// clockwise (with y down). Returns the arc length and the perimeter.
+fn rectArcLength(p: vec2<f32>, h: vec2<f32>, radii: vec4<f32>) -> vec2<f32> {
+    let tl = radii.x;
+    let tr = radii.y;
+    let br = radii.z;
+    let bl = radii.w;
+
+    // Lengths of the sides and corners, going clockwise from the top.
+    let top = 2.0*h.x - tl - tr;
+    let right = 2.0*h.y - tr - br;
+    let bottom = 2.0*h.x - br - bl;
+    let left = 2.0*h.y - bl - tl;
+    let quarter = 1.570796327;
+    let s1 = top + quarter*tr;
+    let s2 = s1 + right;
+    let s3 = s2 + quarter*br;
+    let s4 = s3 + bottom;
+    let s5 = s4 + quarter*bl;
+    let s6 = s5 + left;
+    let perimeter = s6 + quarter*tl;
+
+    var s = 0.0;
+    let r = cornerRadius(p, radii);
+    if(abs(p.x) > h.x - r && abs(p.y) > h.y - r) {
+        let k = p - sign(p)*(h - r);
+        if(p.x > 0.0 && p.y < 0.0) {
+            s = top + r*atan2(k.x, -k.y);
+        } else if(p.x > 0.0) {
+            s = s2 + r*atan2(k.y, k.x);
+        } else if(p.y > 0.0) {
+            s = s4 + r*atan2(-k.x, k.y);
+        } else {
+            s = s6 + r*atan2(-k.y, -k.x);
+        }
+    } else if(h.x - abs(p.x) < h.y - abs(p.y)) {
+        if(p.x > 0.0) {
+            s = s1 + p.y + h.y - tr;
+        } else {
+            s = s5 + h.y - bl - p.y;
+        }
+    } else {
+        if(p.y < 0.0) {
+            s = p.x + h.x - tl;
+        } else {
+            s = s3 + h.x - br - p.x;
+        }
+    }
+    return vec2<f32>(s, perimeter);
+}
+
+/// Miter and bevel joins between round ended segments meeting at a
+/// point, where `q` is relative to the point, `t0` is the tangent at the end
+/// of the first segment and `t1` at the start of the second. Returns the
+/// distances to the region added to the stroke and the region cut away.
+fn sdJoin(q: vec2<f32>, t0: vec2<f32>, t1: vec2<f32>, hw: f32, join: u32, miter_limit: f32) -> vec2<f32> {
+    let turn = det(t0, t1);
+    if(abs(turn) <= 1e-4) {
+        return vec2<f32>(1e10, 1e10);
+    }
+
+    // Normals on the outside of the turn, and their bisector.
+    let n0 = -sign(turn) * rot90(t0);
+    let n1 = -sign(turn) * rot90(t1);
+    let m = normalize(n0 + n1);
+
+    // Region past the ends of both segments on the outside.
+    let wedge = max(-dot(q, t0), dot(q, t1));
+
+    // Miter length relative to the stroke width.
+    let ratio = 1.0 / dot(m, n0);
+
+    if(join == vgerMiterJoin && ratio <= miter_limit) {
+        return vec2<f32>(max(wedge, max(dot(q, n0), dot(q, n1)) - hw), 1e10);
+    }
+
+    // Bevel by cutting off the round join.
+    let bevel = hw * dot(m, n0) - dot(q, m);
+    return vec2<f32>(1e10, max(max(wedge, bevel), length(q) - 2.0*hw));
+}
+
+/// Stroke of the contours of a path. Starting at prim.start, each of the
+/// prim.count contours is stored as (segment count, closed) followed by its
+/// segments.
+///
+/// The union of the round ended segments gives round joins and caps.
+/// Miters and square caps add regions which overlap the segments, so there
+/// are no seams. Bevels and butt caps cut only the round ends of the
+/// segments they belong to, so other parts of the path passing nearby are
+/// left alone. Taking the min over the whole path avoids blending twice
+/// where segments or subpaths overlap.
+fn sdPathStroke(prim: Prim, p: vec2<f32>) -> f32 {
+    let hw = prim.width/2.0;
+    let miter_limit = prim.cv0.x;
+
+    var d = 1e10;
+    var j0 = i32(prim.start);
+    for(var contour = 0u; contour < prim.count; contour = contour + 1u) {
+        let n = i32(cvs.cvs[j0].x);
+        let closed = cvs.cvs[j0].y > 0.0;
+        j0 = j0 + 1;
+        let j1 = j0 + 3*(n - 1);
+
+        let e0 = cvs.cvs[j0];
+        let e1 = cvs.cvs[j1+2];
+        let dir0 = -startTangent(e0, cvs.cvs[j0+1], cvs.cvs[j0+2]);
+        let dir1 = endTangent(cvs.cvs[j1], cvs.cvs[j1+1], e1);
+
+        // Distance to the region cut from the start of the current segment.
+        var cut0 = 1e10;
+        if(closed) {
+            if(lineJoin(prim) != vgerRoundJoin) {
+                cut0 = sdJoin(p - e0, dir1, -dir0, hw, lineJoin(prim), miter_limit).y;
+            }
+        } else if(lineCap(prim) == vgerButtCap) {
+            cut0 = buttCut(p, e0, dir0, hw);
+        }
+
+        for(var i=0; i<n; i = i+1) {
+            let j = j0 + 3*i;
+            let a = cvs.cvs[j];
+            let b = cvs.cvs[j+1];
+            let c = cvs.cvs[j+2];
+
+            // Distance to the region cut from the end of the current segment.
+            var cut1 = 1e10;
+            if(i+1 < n || closed) {
+                if(lineJoin(prim) != vgerRoundJoin) {
+                    let k = j0 + 3*((i+1) % n);
+                    let t0 = endTangent(a, b, c);
+                    let t1 = startTangent(cvs.cvs[k], cvs.cvs[k+1], cvs.cvs[k+2]);
+                    let join = sdJoin(p - c, t0, t1, hw, lineJoin(prim), miter_limit);
+                    d = min(d, join.x);
+                    cut1 = join.y;
+                }
+            } else if(lineCap(prim) == vgerButtCap) {
+                cut1 = buttCut(p, e1, dir1, hw);
+            }
+
+            d = min(d, max(sdPathSegment(p, a, b, c) - hw, -min(cut0, cut1)));
+            cut0 = cut1;
+        }
+
+        if(!closed && lineCap(prim) == vgerSquareCap) {
+            d = min(d, min(sdSquareCap(p, e0, dir0, hw), sdSquareCap(p, e1, dir1, hw)));
+        }
+
+        j0 = j0 + 3*n;
+    }
+
+    return d;
+}
+
+/// Stroke of a band of a polyline, whose points are stored once from
+/// prim.cv0.y to prim.cv1.x. Starting at prim.start, each of the
+/// prim.count runs of consecutive segments crossing the band is stored as
+/// the bits of (first point, segment count), followed by the length of the
+/// polyline before the run. The indices of the ends are stored as bits too. The total length is prim.cv1.y.
+///
+/// Joins at the ends of a run use the neighboring points, and caps only go
+/// on the ends of the whole polyline. As in sdPathStroke, bevels and butt
+/// caps only cut the segments they belong to.
+fn sdPolyline(prim: Prim, p: vec2<f32>) -> f32 {
+    let hw = prim.width/2.0;
+    let miter_limit = prim.cv0.x;
+    let first_point = i32(bitcast<u32>(prim.cv0.y));
+    let last_point = i32(bitcast<u32>(prim.cv1.x));
+
+    let e0 = cvs.cvs[first_point];
+    let e1 = cvs.cvs[last_point];
+    let dir0 = normalize(e0 - cvs.cvs[first_point+1]);
+    let dir1 = normalize(e1 - cvs.cvs[last_point - 1]);
+
+    var d = 1e10;
+    for(var r = 0u; r < prim.count; r = r + 1u) {
+        let run = vec2<i32>(bitcast<vec2<u32>>(cvs.cvs[prim.start + 2u*r]));
+        let j0 = run.x;
+        let n = run.y;
+
+        // Distance to the region cut from the start of the current segment.
+        var cut0 = 1e10;
+        if(j0 > first_point) {
+            if(lineJoin(prim) != vgerRoundJoin) {
+                let v = cvs.cvs[j0];
+                let t0 = normalize(v - cvs.cvs[j0 - 1]);
+                let t1 = normalize(cvs.cvs[j0+1] - v);
+                cut0 = sdJoin(p - v, t0, t1, hw, lineJoin(prim), miter_limit).y;
+            }
+        } else if(lineCap(prim) == vgerButtCap) {
+            cut0 = buttCut(p, e0, dir0, hw);
+        }
+
+        for(var i = j0; i < j0+n; i = i+1) {
+            let a = cvs.cvs[i];
+            let b = cvs.cvs[i+1];
+
+            // Distance to the region cut from the end of the current segment.
+            var cut1 = 1e10;
+            if(i+1 < last_point) {
+                if(lineJoin(prim) != vgerRoundJoin) {
+                    let t0 = normalize(b - a);
+                    let t1 = normalize(cvs.cvs[i+2] - b);
+                    let join = sdJoin(p - b, t0, t1, hw, lineJoin(prim), miter_limit);
+                    d = min(d, join.x);
+                    cut1 = join.y;
+                }
+            } else if(lineCap(prim) == vgerButtCap) {
+                cut1 = buttCut(p, e1, dir1, hw);
+            }
+
+            d = min(d, max(length(closestPointInSegment(a - p, b - p)) - hw, -min(cut0, cut1)));
+            cut0 = cut1;
+        }
+
+        if(lineCap(prim) == vgerSquareCap) {
+            if(j0 == first_point) {
+                d = min(d, sdSquareCap(p, e0, dir0, hw));
+            }
+            if(j0+n == last_point) {
+                d = min(d, sdSquareCap(p, e1, dir1, hw));
+            }
+        }
+    }
+
+    return d;
+}
+
+/// Area between the line segments through the points
+/// prim.start..prim.start+prim.count+1 and the horizontal line y = prim.cv0.x,
+/// ending at x = prim.cv0.y and x = prim.cv1.x.
+///
+/// The points increase in x, so the segment above or below a point can be
+/// found with a binary search.
+fn sdArea(prim: Prim, p: vec2<f32>) -> f32 {
+    let j0 = prim.start;
+    var lo = 0u;
+    var hi = prim.count;
+    loop {
+        if(hi - lo <= 1u) {
+            break;
+        }
+        let mid = (lo + hi) / 2u;
+        if(cvs.cvs[j0+mid].x <= p.x) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    let a = cvs.cvs[j0+lo];
+    let b = cvs.cvs[j0+lo+1u];
+    let t = clamp((p.x - a.x) / max(b.x - a.x, 1e-10), 0.0, 1.0);
+    let y = mix(a.y, b.y, t);
+
+    // Signed distances, positive below the segment and the baseline.
+    let baseline = prim.cv0.x;
+    let curve = sign(p.y - y) * length(closestPointInSegment(a - p, b - p));
+    let base = p.y - baseline;
+
+    // Inside if between the two.
+    let s = select(-1.0, 1.0, baseline >= y);
+    let ends = max(prim.cv0.y - p.x, p.x - prim.cv1.x);
+    return max(max(-s*curve, s*base), ends);
+}
+
+/// Min, max and RMS of a waveform at position `x` among its entries,
+/// interpolated between entry centers.
+fn waveformEntry(prim: Prim, x: f32) -> vec3<f32> {
+    let n = i32(prim.count);
+    let c = clamp(x - 0.5, 0.0, f32(n - 1));
+    let i = i32(floor(c));
+    let j = min(i + 1, n - 1);
+    let k = i32(prim.start);
+    let a = vec3<f32>(cvs.cvs[k + 2*i], cvs.cvs[k + 2*i + 1].x);
+    let b = vec3<f32>(cvs.cvs[k + 2*j], cvs.cvs[k + 2*j + 1].x);
+    return mix(a, b, c - f32(i));
+}
+
+/// Waveform envelope in the rect prim.cv0..prim.cv1. The entries at
+/// prim.start are pairs of (min, max) and (rms, 0), and the ends of the
+/// rect are at positions prim.cv2 among them. Each column covers the
+/// entries under its pixel, so the envelope is continuous.
+fn sdWaveform(prim: Prim, p: vec2<f32>, fw: f32, rms: bool) -> f32 {
+    let x0 = prim.cv0.x;
+    let x1 = prim.cv1.x;
+    let scale = (prim.cv2.y - prim.cv2.x) / (x1 - x0);
+    let e = prim.cv2.x + (p.x - x0) * scale;
+    let h = 0.5 * fw * scale;
+
+    let a = waveformEntry(prim, e - h);
+    let b = waveformEntry(prim, e + h);
+    var env = vec3<f32>(min(a.x, b.x), max(a.y, b.y), max(a.z, b.z));
+
+    // Entry centers in between.
+    let first = max(i32(ceil(e - h - 0.5)), 0);
+    let last = min(i32(floor(e + h - 0.5)), i32(prim.count) - 1);
+    for(var i = first; i <= min(last, first + 15); i = i+1) {
+        let j = i32(prim.start) + 2*i;
+        let v = vec3<f32>(cvs.cvs[j], cvs.cvs[j+1].x);
+        env = vec3<f32>(min(env.x, v.x), max(env.y, v.y), max(env.z, v.z));
+    }
+
+    var lo = env.x;
+    var hi = env.y;
+    if(rms) {
+        lo = -env.z;
+        hi = env.z;
+    }
+
+    let center = 0.5*(prim.cv0.y + prim.cv1.y);
+    let half_height = 0.5*(prim.cv1.y - prim.cv0.y);
+    let top = center - hi * half_height;
+    let bottom = center - lo * half_height;
+
+    // Widen by half a pixel so silence still shows as a line.
+    let d = max(top - p.y, p.y - bottom) - 0.5*fw;
+    return max(d, max(x0 - p.x, p.x - x1));
+}
+
+/// Coverage of a pixel by the lines of width 2*hw nearest to it, at
+/// distance `d`. Lines thinner than a pixel are drawn a pixel wide and
+/// fainter, which avoids aliasing.
+fn lineCoverage(d: f32, hw: f32, fw: f32) -> f32 {
+    let w = max(hw, 0.5*fw);
+    return (hw / w) * (1.0 - smoothstep(w - 0.5*fw, w + 0.5*fw, d));
+}
+
+/// Coverage of a pixel by the dots of radius r nearest to it, at distance
+/// `d`. Like lines, dots smaller than a pixel are drawn a pixel wide and
+/// fainter.
+fn dotCoverage(d: f32, r: f32, fw: f32) -> f32 {
+    let w = max(r, 0.5*fw);
+    return (r / w) * (r / w) * (1.0 - smoothstep(w - 0.5*fw, w + 0.5*fw, d));
+}
+
+/// Coverage of a pixel by the lines or dots of a grid, where `dm` is the
+/// offset from the nearest grid point.
+fn gridPointCoverage(dm: vec2<f32>, hw: f32, fw: vec2<f32>, dots: bool) -> f32 {
+    if(dots) {
+        return dotCoverage(length(dm), hw, max(fw.x, fw.y));
+    }
+    return max(lineCoverage(dm.x, hw, fw.x), lineCoverage(dm.y, hw, fw.y));
+}
+
+/// Coverage of a pixel by the minor and major lines or dots of a grid.
+/// They fade out as they get closer together than a few pixels.
+fn gridCoverage(prim: Prim, p: vec2<f32>, fw: vec2<f32>) -> vec2<f32> {
+    let hw = prim.width/2.0;
+    let pixel = max(fw.x, fw.y);
+    let dots = prim.cv1.y > 0.0;
+
+    let spacing = prim.cv0.x;
+    let dm = abs(fract(p/spacing + 0.5) - 0.5) * spacing;
+    var minor = gridPointCoverage(dm, hw, fw, dots);
+    minor *= smoothstep(4.0, 12.0, spacing / pixel);
+
+    var major = 0.0;
+    if(prim.cv0.y >= 1.0) {
+        let major_spacing = spacing * prim.cv0.y;
+        let dM = abs(fract(p/major_spacing + 0.5) - 0.5) * major_spacing;
+        major = gridPointCoverage(dM, hw, fw, dots);
+        major *= smoothstep(2.0, 6.0, major_spacing / pixel);
+    }
+
+    return vec2<f32>(minor, major);
+}
+
+/// Sector of a ring, which is the arc at the middle of the ring stroked
+/// with the width of the ring, so its ends can be capped like strokes.
+fn sdSector(prim: Prim, p: vec2<f32>) -> f32 {
+    let hw = prim.width/2.0;
+    if(lineCap(prim) == vgerButtCap) {
+        return sdArc2(p - prim.cv0, prim.cv1, prim.cv2, prim.radius, hw);
+    }
+    let d = sdArc(p - prim.cv0, prim.cv1, prim.cv2, prim.radius, hw);
+    if(lineCap(prim) == vgerSquareCap) {
+        // Ends and outward tangents in the frame of the arc.
+        let pp = (p - prim.cv0) * mat2x2<f32>(prim.cv1, vec2<f32>(-prim.cv1.y, prim.cv1.x));
+        let sc = prim.cv2;
+        let e0 = prim.radius * vec2<f32>(-sc.x, sc.y);
+        let e1 = prim.radius * sc;
+        return sdCaps(pp, d, e0, -vec2<f32>(sc.y, sc.x), e1, vec2<f32>(sc.y, -sc.x), hw, lineCap(prim));
+    }
+    return d;
+}
+
 struct BBox {
     min: vec2<f32>,
     max: vec2<f32>,
@@ -333,9 +1213,10 @@ fn sdPrimBounds(prim: Prim) -> BBox {
                 b = expand(b, cvs.cvs[i32(prim.start)+i]);
             }
         }
-        case 7u: { // vgerSegment
-            b.min = min(prim.cv0, prim.cv1);
-            b.max = max(prim.cv0, prim.cv1);
+        case 7u: { // vgerWire
+            let c = wireControl(prim.cv0, prim.cv1, prim.radius);
+            b.min = min(min(prim.cv0, prim.cv1), c);
+            b.max = max(max(prim.cv0, prim.cv1), c);
         }
         case 8u: { // vgerGlyph
             b.min = prim.cv0;
@@ -356,6 +1237,70 @@ fn sdPrimBounds(prim: Prim) -> BBox {
             b.min = prim.cv0;
             b.max = prim.cv1;
         }
+        case 12u: { // vgerPathStroke
+            b.min = vec2<f32>(1e10, 1e10);
+            b.max = -b.min;
+            var j = i32(prim.start);
+            for(var contour = 0u; contour < prim.count; contour = contour + 1u) {
+                let n = 3 * i32(cvs.cvs[j].x);
+                for(var i: i32 = 1; i <= n; i = i+1) {
+                    b = expand(b, cvs.cvs[j+i]);
+                }
+                j = j + n + 1;
+            }
+        }
+        case 13u, 14u: { // vgerEllipse, vgerEllipseStroke
+            b.min = prim.cv0 - prim.cv1;
+            b.max = prim.cv0 + prim.cv1;
+        }
+        case 15u: { // vgerSector
+            b.min = prim.cv0 - prim.radius - prim.width/2.0;
+            b.max = prim.cv0 + prim.radius + prim.width/2.0;
+        }
+        case 16u, 17u, 18u, 19u: { // vgerPolygon, vgerPolygonStroke, vgerStar, vgerStarStroke
+            b.min = prim.cv0 - prim.radius;
+            b.max = prim.cv0 + prim.radius;
+        }
+        case 20u: { // vgerInsetShadow
+            b.min = cvs.cvs[prim.start];
+            b.max = cvs.cvs[prim.start+1u];
+        }
+        case 22u: { // vgerBorder
+            b.min = prim.cv0;
+            b.max = prim.cv1;
+        }
+        case 23u: { // vgerVariableCurve
+            b.min = vec2<f32>(1e10, 1e10);
+            b.max = -b.min;
+            for(var i: i32 = 0; i < i32(prim.count); i = i+1) {
+                let j = i32(prim.start) + 5*i;
+                b = expand(b, cvs.cvs[j]);
+                b = expand(b, cvs.cvs[j+1]);
+                b = expand(b, cvs.cvs[j+2]);
+            }
+        }
+        case 24u: { // vgerPolyline
+            b.min = vec2<f32>(1e10, 1e10);
+            b.max = -b.min;
+            for(var i = i32(bitcast<u32>(prim.cv0.y)); i <= i32(bitcast<u32>(prim.cv1.x)); i = i+1) {
+                b = expand(b, cvs.cvs[i]);
+            }
+        }
+        case 25u: { // vgerArea
+            b.min = vec2<f32>(cvs.cvs[prim.start].x, prim.cv0.x);
+            b.max = b.min;
+            for(var i: i32 = 0; i <= i32(prim.count); i = i+1) {
+                b = expand(b, cvs.cvs[i32(prim.start)+i]);
+            }
+        }
+        case 26u, 27u: { // vgerWaveform, vgerWaveformRms
+            b.min = prim.cv0;
+            b.max = prim.cv1;
+        }
+        case 28u: { // vgerGrid
+            b.min = prim.quad_bounds_min;
+            b.max = prim.quad_bounds_max;
+        }
         default: {}
     }
     return b;
@@ -401,37 +1346,86 @@ fn bezierTest(p: vec2<f32>, A: vec2<f32>, B: vec2<f32>, C: vec2<f32>) -> bool {
 fn sdPrim(prim: Prim, p: vec2<f32>, filterWidth: f32) -> f32 {
     var d = 1e10;
     var s = 1.0;
+    var w = 0;
     switch(prim.prim_type) {
         case 0u: { // vgerCircle
             d = sdCircle(p - prim.cv0, prim.radius);
         }
         case 1u: { // vgerArc
-            d = sdArc2(p - prim.cv0, prim.cv1, prim.cv2, prim.radius, prim.width/2.0);
+            if(prim.count > 0u) {
+                let pp = (p - prim.cv0) * mat2x2<f32>(prim.cv1, vec2<f32>(-prim.cv1.y, prim.cv1.x));
+                let aperture = atan2(prim.cv2.x, prim.cv2.y);
+                let theta = clamp(atan2(pp.x, pp.y), -aperture, aperture);
+                let n = vec2<f32>(sin(theta), cos(theta));
+                // Dashes of full circles wrap around.
+                let closed = prim.cv2.y <= -1.0;
+                d = sdDashedStroke(prim, pp, prim.radius*n, vec2<f32>(n.y, -n.x),
+                                   (theta + aperture)*prim.radius, 2.0*aperture*prim.radius, closed);
+            } else if(prim.cv2.y <= -1.0) {
+                // Full circle. The pie would leave a seam.
+                d = abs(sdCircle(p - prim.cv0, prim.radius)) - prim.width/2.0;
+            } else {
+                d = sdArc2(p - prim.cv0, prim.cv1, prim.cv2, prim.radius, prim.width/2.0);
+            }
         }
         case 2u: { // vgerRect
             let center = 0.5*(prim.cv1 + prim.cv0);
             let size = prim.cv1 - prim.cv0;
-            d = sdBox(p - center, 0.5*size, prim.radius);
+            d = sdRoundedBox(p - center, 0.5*size, cornerRadii(prim.start));
         }
         case 3u: { // vgerRectStroke
             let center = 0.5*(prim.cv1 + prim.cv0);
             let size = prim.cv1 - prim.cv0;
-            d = abs(sdBox(p - center, 0.5*size, prim.radius)) - prim.width/2.0;
+            let radii = cornerRadii(prim.start);
+            let e = abs(sdRoundedBox(p - center, 0.5*size, radii) - prim.radius);
+            d = e - prim.width/2.0;
+            if(prim.count > 0u) {
+                let s = rectArcLength(p - center, 0.5*size, radii);
+                let da = dashDistance(prim, prim.start + 2u, s.x, s.y, true);
+                d = sdDash(da, e, prim.width/2.0, lineCap(prim));
+            }
         }
         case 4u: { // vgerBezier
-            d = sdBezierApprox(p, prim.cv0, prim.cv1, prim.cv2) - prim.width/2.0;
+            let hw = prim.width/2.0;
+            let dir0 = -startTangent(prim.cv0, prim.cv1, prim.cv2);
+            let dir1 = endTangent(prim.cv0, prim.cv1, prim.cv2);
+            if(prim.count > 0u) {
+                let t = bezierClosestT(p, prim.cv0, prim.cv1, prim.cv2);
+                let a = prim.cv1 - prim.cv0;
+                let b = prim.cv0 - 2.0*prim.cv1 + prim.cv2;
+                var tangent = -dir0;
+                if(t >= 1.0) {
+                    tangent = dir1;
+                } else if(t > 0.0) {
+                    tangent = normalize(a + b*t);
+                }
+                d = sdDashedStroke(prim, p, prim.cv0 + (2.0*a + b*t)*t, tangent,
+                                   bezierLength(prim.cv0, prim.cv1, prim.cv2, t),
+                                   bezierLength(prim.cv0, prim.cv1, prim.cv2, 1.0), false);
+            } else {
+                d = sdBezierApprox(p, prim.cv0, prim.cv1, prim.cv2) - hw;
+                d = sdCaps(p, d, prim.cv0, dir0, prim.cv2, dir1, hw, lineCap(prim));
+            }
         }
         case 5u: { // vgerSegment
-            d = sdSegment2(p, prim.cv0, prim.cv1, prim.width);
+            let hw = prim.width/2.0;
+            let dir = normalize(prim.cv1 - prim.cv0);
+            if(prim.count > 0u) {
+                d = sdDashedStroke(prim, p, prim.cv0, dir, 0.0, length(prim.cv1 - prim.cv0), false);
+            } else {
+                d = length(closestPointInSegment(prim.cv0 - p, prim.cv1 - p)) - hw;
+                d = sdCaps(p, d, prim.cv0, -dir, prim.cv1, dir, hw, lineCap(prim));
+            }
         }
         case 6u: { // vgerCurve
             for(var i=0; i<i32(prim.count); i = i+1) {
                 let j = i32(prim.start) + 3*i;
                 d = min(d, sdBezierApprox(p, cvs.cvs[j], cvs.cvs[j+1], cvs.cvs[j+2]));
             }
+            d = d - prim.width/2.0;
         }
-        case 7u: { // vgerSegment
-            d = sdSegment2(p, prim.cv0, prim.cv1, prim.width);
+        case 7u: { // vgerWire
+            d = sdWire(p, prim.cv0, prim.cv1, prim.radius) - prim.width/2.0;
         }
         case 8u: { // vgerGlyph
             let center = 0.5*(prim.cv1 + prim.cv0);
@@ -463,21 +1457,27 @@ fn sdPrim(prim: Prim, p: vec2<f32>, filterWidth: f32) -> f32 {
                 }
 
                 if(!skip) {
-                    d = min(d, sdBezier(p, a, b, c));
+                    d = min(d, sdPathSegment(p, a, b, c));
                 }
 
                 if(lineTest(p, a, c)) {
                     s = -s;
+                    w = w + select(-1, 1, c.y > a.y);
                 }
 
                 // Flip if inside area between curve and line.
                 if(!skip) {
                     if(bezierTest(p, a, b, c)) {
                         s = -s;
+                        // The area is wound in the direction of the triangle.
+                        w = w + select(-1, 1, det(b - a, c - a) > 0.0);
                     }
                 }
 
             }
+            if(fillRule(prim) == vgerNonZero) {
+                s = select(1.0, -1.0, w != 0);
+            }
             d = d * s;
             break;
         }
@@ -486,11 +1486,89 @@ fn sdPrim(prim: Prim, p: vec2<f32>, filterWidth: f32) -> f32 {
             let size = prim.cv1 - prim.cv0;
             d = sdBox(p - center, 0.5*size, prim.radius);
         }
+        case 12u: { // vgerPathStroke
+            d = sdPathStroke(prim, p);
+        }
+        case 13u: { // vgerEllipse
+            d = sdEllipse(p - prim.cv0, prim.cv1);
+        }
+        case 14u: { // vgerEllipseStroke
+            d = abs(sdEllipse(p - prim.cv0, prim.cv1)) - prim.width/2.0;
+        }
+        case 15u: { // vgerSector
+            d = sdSector(prim, p);
+        }
+        case 16u: { // vgerPolygon
+            d = sdRoundedPolygon(p - prim.cv0, prim.radius, prim.cv1.x, prim.cv1.y, prim.cv2.x);
+        }
+        case 17u: { // vgerPolygonStroke
+            d = abs(sdRoundedPolygon(p - prim.cv0, prim.radius, prim.cv1.x, prim.cv1.y, prim.cv2.x)) - prim.width/2.0;
+        }
+        case 18u: { // vgerStar
+            d = sdStar(p - prim.cv0, prim.radius, prim.cv2.x, prim.cv1.x, prim.cv1.y);
+        }
+        case 19u: { // vgerStarStroke
+            d = abs(sdStar(p - prim.cv0, prim.radius, prim.cv2.x, prim.cv1.x, prim.cv1.y)) - prim.width/2.0;
+        }
+        case 20u: { // vgerInsetShadow
+            // Box the shadow is inside of, with its radii following.
+            let j = prim.start;
+            let center = 0.5*(cvs.cvs[j] + cvs.cvs[j+1u]);
+            let size = cvs.cvs[j+1u] - cvs.cvs[j];
+            d = sdRoundedBox(p - center, 0.5*size, cornerRadii(j+2u));
+        }
+        case 22u: { // vgerBorder
+            let center = 0.5*(prim.cv1 + prim.cv0);
+            let size = prim.cv1 - prim.cv0;
+            let j = prim.start;
+            let inner_center = 0.5*(cvs.cvs[j] + cvs.cvs[j+1u]);
+            let inner_size = cvs.cvs[j+1u] - cvs.cvs[j];
+            d = max(sdRoundedBox(p - center, 0.5*size, cornerRadii(j+4u)),
+                    -sdRoundedBox(p - inner_center, 0.5*inner_size, cornerRadii(j+2u)));
+        }
+        case 23u: { // vgerVariableCurve
+            d = sdVariableCurve(prim, p);
+        }
+        case 24u: { // vgerPolyline
+            d = sdPolyline(prim, p);
+        }
+        case 25u: { // vgerArea
+            d = sdArea(prim, p);
+        }
+        case 26u: { // vgerWaveform
+            d = sdWaveform(prim, p, filterWidth, false);
+        }
+        case 27u: { // vgerWaveformRms
+            d = sdWaveform(prim, p, filterWidth, true);
+        }
         default: { }
     }
     return d;
 }
 
+/// Side of a border (top, right, bottom, left) a point is on. Sides are
+/// split by the lines from the outer to the inner corners, where the
+/// point is the same fraction of the way across both sides.
+fn borderSide(prim: Prim, p: vec2<f32>) -> u32 {
+    let j = prim.start;
+    let inner_min = cvs.cvs[j];
+    let inner_max = cvs.cvs[j+1u];
+    let widths = vec4<f32>(inner_min.y - prim.cv0.y, prim.cv1.x - inner_max.x,
+                           prim.cv1.y - inner_max.y, inner_min.x - prim.cv0.x);
+    let insets = vec4<f32>(p.y - prim.cv0.y, prim.cv1.x - p.x,
+                           prim.cv1.y - p.y, p.x - prim.cv0.x);
+
+    var side = 0u;
+    var closest = 1e10;
+    for(var i = 0u; i < 4u; i = i+1u) {
+        if(widths[i] > 0.0 && insets[i] / widths[i] < closest) {
+            closest = insets[i] / widths[i];
+            side = i;
+        }
+    }
+    return side;
+}
+
 struct XForms {
     xforms: array<mat4x4<f32>>,
 };
@@ -585,6 +1663,9 @@ struct Paint {              // align  size
     outer_color: vec4<f32>, // 16     16
 };
 
+/// Paint image for gradients along the length of strokes.
+const vgerStrokeGradient = -2;
+
 struct Paints {
     paints: array<Paint>,
 };
@@ -668,11 +1749,97 @@ fn gaussian(x: f32, sigma: f32) -> f32 {
   return exp(-(x * x) / (2.0 * sigma * sigma)) / (sqrt(2.0 * pi) * sigma);
 }
 
-fn roundedBoxShadowX(x: f32, y: f32, sigma: f32, corner: f32, halfSize: vec2<f32>) -> f32 {
-    let delta = min(halfSize.y - corner - abs(y), 0.0);
-    let curved = halfSize.x - corner + sqrt(max(0.0, corner * corner - delta * delta));
-    let integral = 0.5 + 0.5 * erf((x + vec2(-curved, curved)) * (sqrt(0.5) / sigma));
-    return integral.y - integral.x;
+fn roundedBoxShadowX(x: f32, y: f32, sigma: f32, radii: vec4<f32>, halfSize: vec2<f32>) -> f32 {
+    // Radii of the left and right corners on this side of the box.
+    let corner = select(radii.wz, radii.xy, y < 0.0);
+    let delta = min(halfSize.y - corner - abs(y), vec2<f32>(0.0));
+    let curved = halfSize.x - corner + sqrt(max(vec2<f32>(0.0), corner * corner - delta * delta));
+    let integral = 0.5 + 0.5 * erf((x + vec2(curved.x, -curved.y)) * (sqrt(0.5) / sigma));
+    return integral.x - integral.y;
+}
+
+/// Glow outside a shape, given the distance to it.
+fn glowFalloff(d: f32, glow: f32) -> f32 {
+    let g = 1.0 - clamp(d / glow, 0.0, 1.0);
+    return g * g;
+}
+
+/// Coverage of a glyph at atlas coordinates t. When glowing or casting a
+/// shadow, the quad extends past the glyph's region of the atlas, which is
+/// stored in the glyph's control vertices.
+fn atlasCoverage(prim: Prim, t: vec2<f32>, size: vec2<f32>) -> f32 {
+    if(any(t < prim.cv0) || any(t > prim.cv1)) {
+        return 0.0;
+    }
+    let uv = t / size;
+    if(prim.prim_type == 8u) { // vgerGlyph
+        return textureSampleLevel(glyph_atlas, samp, uv, 0.0).r;
+    }
+    return textureSampleLevel(color_atlas, color_samp, uv, 0.0).a;
+}
+
+/// Coverage of a glyph blurred by a gaussian with standard deviation
+/// `blur` in atlas pixels, sampled at t and on two rings around it.
+fn blurredAtlasCoverage(prim: Prim, t: vec2<f32>, size: vec2<f32>, blur: f32) -> f32 {
+    // Each sample is weighted by the gaussian and the area it stands for.
+    var sum = 0.25 * atlasCoverage(prim, t, size);
+    var total = 0.25;
+    for(var ring = 1; ring <= 2; ring++) {
+        let r = blur * f32(ring);
+        let w = exp(-0.5 * f32(ring * ring)) * f32(ring) / 4.0;
+        for(var i = 0; i < 8; i++) {
+            let angle = (f32(i) + 0.5 * f32(ring - 1)) * 0.785398163;
+            sum += w * atlasCoverage(prim, t + r*vec2<f32>(cos(angle), sin(angle)), size);
+            total += w;
+        }
+    }
+    return sum / total;
+}
+
+/// Blur of a shadow prim in texture coordinates.
+fn textureBlur(prim: Prim) -> f32 {
+    let scale = (prim.tex_bounds_max.x - prim.tex_bounds_min.x) / max(prim.quad_bounds_max.x - prim.quad_bounds_min.x, 1e-6);
+    return shadowBlur(prim) * scale;
+}
+
+/// Glow around a glyph. Glyphs don't have distance fields, so the
+/// distance is estimated by searching rings around t, stopping at the
+/// first ring which reaches the glyph. Only called for glowing paints.
+fn glyphGlow(prim: Prim, t: vec2<f32>, size: vec2<f32>, glow: f32) -> f32 {
+    if(atlasCoverage(prim, t, size) > 0.5) {
+        return 1.0;
+    }
+    for(var ring = 1; ring <= 3; ring++) {
+        let r = glow * f32(ring) / 3.0;
+        for(var i = 0; i < 8; i++) {
+            let angle = f32(i) * 0.785398163;
+            if(atlasCoverage(prim, t + r*vec2<f32>(cos(angle), sin(angle)), size) > 0.5) {
+                return glowFalloff(r, glow);
+            }
+        }
+    }
+    return 0.0;
+}
+
+/// Coverage of a blurred rounded box.
+fn roundedBoxShadow(point: vec2<f32>, half_size: vec2<f32>, radii: vec4<f32>, blur_radius: f32) -> f32 {
+    let low = point.y - half_size.y;
+    let high = point.y + half_size.y;
+    let start = clamp(-3.0 * blur_radius, low, high);
+    let end = clamp(3.0 * blur_radius, low, high);
+
+    let step = (end - start) / 4.0;
+    var y = start + step * 0.5;
+    var value = 0.0;
+    for (var i: i32 = 0; i < 4; i++) {
+        value += roundedBoxShadowX(point.x, point.y - y, blur_radius, radii, half_size) * gaussian(y, blur_radius) * step;
+        y += step;
+    }
+
+    // The samples don't quite add up to the whole gaussian, so divide by
+    // their total to fully cover the inside of the box.
+    let total = 3.0 * blur_radius * (gaussian(0.75 * blur_radius, blur_radius) + gaussian(2.25 * blur_radius, blur_radius));
+    return value / total;
 }
 
 @fragment
@@ -680,9 +1847,17 @@ fn fs_main(
     in: VertexOutput,
 ) -> @location(0) vec4<f32> {
 
-    let fw = length(fwidth(in.t));
+    let fw2 = fwidth(in.t);
+    let fw = length(fw2);
     let prim = prims.prims[in.prim_index];
-    let paint = paints.paints[prim.paint];
+    var paint_index = prim.paint;
+    if(prim.prim_type == 22u && prim.count > 0u) { // vgerBorder
+        let side = borderSide(prim, in.t);
+        let paints = vec4<u32>(bitcast<vec2<u32>>(cvs.cvs[prim.start+6u]),
+                               bitcast<vec2<u32>>(cvs.cvs[prim.start+7u]));
+        paint_index = paints[side];
+    }
+    let paint = paints.paints[paint_index];
     let scissor = scissors.scissors[prim.scissor];
 
     // Look up glyph alpha (if not a glyph, still have to because of wgsl).
@@ -698,31 +1873,62 @@ fn fs_main(
 
     let s = scissor_mask(scissor, in.p);
     
-    if(prim.prim_type == 2u && prim.cv2.x > 0.0) {
-        let p = in.t;
-        let blur_radius = prim.cv2.x;
+    if(prim.prim_type == 2u && (prim.cv2.x > 0.0 || shadowBlur(prim) > 0.0)) {
+        let blur_radius = length(vec2<f32>(prim.cv2.x, shadowBlur(prim)));
         let center = 0.5*(prim.cv1 + prim.cv0);
         let half_size = 0.5*(prim.cv1 - prim.cv0);
-        let point = p - center;
-        
-        let low = point.y - half_size.y;
-        let high = point.y + half_size.y;
-        let start = clamp(-3.0 * blur_radius, low, high);
-        let end = clamp(3.0 * blur_radius, low, high);
-        
-        let step = (end - start) / 4.0;
-        var y = start + step * 0.5;
+        let value = roundedBoxShadow(in.t - center, half_size, cornerRadii(prim.start), blur_radius);
+        return s * vec4<f32>(paint.inner_color.rgb, value * paint.inner_color.a);
+    }
+
+    if(prim.prim_type == 28u) { // vgerGrid
+        let coverage = gridCoverage(prim, in.t, fw2);
+        let minor = apply(paint, in.t);
+        let major_paint = select(prim.paint, bitcast<u32>(prim.cv1.x), prim.count > 0u);
+        let major = apply(paints.paints[major_paint], in.t);
+
+        // Major lines over minor.
+        let a_minor = minor.a * coverage.x;
+        let a_major = major.a * coverage.y;
+        let a = a_major + a_minor * (1.0 - a_major);
+        let rgb = major.rgb * a_major + minor.rgb * a_minor * (1.0 - a_major);
+        return s * vec4<f32>(rgb / max(a, 1e-6), a);
+    }
+
+    if(prim.prim_type == 20u) { // vgerInsetShadow
+        let center = 0.5*(prim.cv1 + prim.cv0);
+        let half_size = 0.5*(prim.cv1 - prim.cv0);
+        let radii = cornerRadii(prim.start + 4u);
+
+        // When casting a shadow itself, blur the inner box and the clip.
+        let blur = shadowBlur(prim);
+        let blur_radius = length(vec2<f32>(prim.cv2.x, blur));
         var value = 0.0;
-        for (var i: i32 = 0; i < 4; i++) {
-            value += roundedBoxShadowX(point.x, point.y - y, blur_radius, prim.radius, half_size) * gaussian(y, blur_radius) * step;
-            y += step;
+        if(blur_radius > 0.0) {
+            value = 1.0 - roundedBoxShadow(in.t - center, half_size, radii, blur_radius);
+        } else {
+            value = smoothstep(-fw/2.0, fw/2.0, sdRoundedBox(in.t - center, half_size, radii));
+        }
+
+        // Clip to the inside of the box.
+        let d = sdPrim(prim, in.t, fw);
+        if(blur > 0.0) {
+            value *= 0.5 - 0.5*erf(vec2<f32>(d * sqrt(0.5) / blur)).x;
+        } else {
+            value *= 1.0 - smoothstep(-fw/2.0, fw/2.0, d);
         }
-        
         return s * vec4<f32>(paint.inner_color.rgb, value * paint.inner_color.a);
     }
 
     if(prim.prim_type == 8u) { // vgerGlyph
-        if (mask.r <= 0.0) {
+        var a = mask.r;
+        if(shadowBlur(prim) > 0.0) {
+            a = blurredAtlasCoverage(prim, in.t, in.size, textureBlur(prim));
+        } else if(paint.glow > 0.0) {
+            a = max(atlasCoverage(prim, in.t, in.size), glyphGlow(prim, in.t, in.size, paint.glow));
+        }
+
+        if (a <= 0.0) {
             discard;
         }
 
@@ -730,11 +1936,7 @@ fn fs_main(
 
         // XXX: using toLinear is a bit of a guess. Gets us closer
         // to matching the glyph atlas in the output.
-        var color = vec4<f32>(c.rgb, c.a * mask.r);
-
-        //if(glow) {
-        //    color.a *= paint.glow;
-        //}
+        var color = vec4<f32>(c.rgb, c.a * a);
 
         return s * color;
     }
@@ -747,9 +1949,11 @@ fn fs_main(
         // to matching the glyph atlas in the output.
         var color = vec4<f32>(color_mask.rgb, c.a * color_mask.a);
 
-        //if(glow) {
-        //    color.a *= paint.glow;
-        //}
+        if(paint.glow > 0.0) {
+            let a = atlasCoverage(prim, in.t, in.size);
+            let g = glyphGlow(prim, in.t, in.size, paint.glow);
+            color = vec4<f32>(mix(c.rgb, color_mask.rgb, a), c.a * max(a, g));
+        }
 
         return s * color;
     }
@@ -758,13 +1962,16 @@ fn fs_main(
 
         let c = paint.inner_color;
 
+        var a = color_mask.a;
+        if(shadowBlur(prim) > 0.0) {
+            a = blurredAtlasCoverage(prim, in.t, in.size, textureBlur(prim));
+        } else if(paint.glow > 0.0) {
+            a = max(atlasCoverage(prim, in.t, in.size), glyphGlow(prim, in.t, in.size, paint.glow));
+        }
+
         // XXX: using toLinear is a bit of a guess. Gets us closer
         // to matching the glyph atlas in the output.
-        var color = vec4<f32>(c.rgb, c.a * color_mask.a);
-
-        //if(glow) {
-        //    color.a *= paint.glow;
-        //}
+        var color = vec4<f32>(c.rgb, c.a * a);
 
         return s * color;
     }
@@ -773,6 +1980,73 @@ fn fs_main(
     if paint.image == -1 {
         color = apply(paint, in.t);
     }
+    if paint.image == vgerStrokeGradient {
+        color = apply(paint, vec2<f32>(strokeFraction(prim, in.t), 0.0));
+    }
+
+    if(paint.glow > 0.0) {
+        let a = max(1.0-smoothstep(-fw/2.0,fw/2.0,d), glowFalloff(d, paint.glow));
+        return s * vec4<f32>(color.rgb, color.a * a);
+    }
+
+    if(shadowBlur(prim) > 0.0) {
+        // Blurring a half-plane gives the normal CDF of the distance, which
+        // approximates the blurred coverage of other shapes.
+        let coverage = 0.5 - 0.5*erf(vec2<f32>(d * sqrt(0.5) / shadowBlur(prim))).x;
+        return s * vec4<f32>(color.rgb, color.a * coverage);
+    }
 
     return s * mix(vec4<f32>(color.rgb,0.0), color, 1.0-smoothstep(-fw/2.0,fw/2.0,d) );
 }
+
+struct MeshVertex {
+    color: vec4<f32>,
+    position: vec2<f32>,
+    xform: u32,
+    scissor: u32,
+};
+
+struct MeshVertices {
+    vertices: array<MeshVertex>,
+};
+
+@group(0)
+@binding(5)
+var<storage> mesh_vertices: MeshVertices;
+
+struct MeshIndices {
+    indices: array<u32>,
+};
+
+@group(0)
+@binding(6)
+var<storage> mesh_indices: MeshIndices;
+
+struct MeshOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+
+    /// Point transformed by current transform.
+    @location(1) p: vec2<f32>,
+
+    @location(2) @interpolate(flat) scissor: u32,
+};
+
+@vertex
+fn vs_mesh(@builtin(vertex_index) vid: u32) -> MeshOutput {
+    var out: MeshOutput;
+
+    let v = mesh_vertices.vertices[mesh_indices.indices[vid]];
+    out.p = (xforms.xforms[v.xform] * vec4<f32>(v.position, 0.0, 1.0)).xy;
+    out.position = vec4<f32>((2.0 * out.p / uniforms.size - 1.0) * vec2<f32>(1.0, -1.0), 0.0, 1.0);
+    out.color = v.color;
+    out.scissor = v.scissor;
+
+    return out;
+}
+
+@fragment
+fn fs_mesh(in: MeshOutput) -> @location(0) vec4<f32> {
+    let s = scissor_mask(scissors.scissors[in.scissor], in.p);
+    return s * in.color;
+}
@@ -0,0 +1,111 @@
+/// Min, max and mean square of a run of samples.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Envelope {
+    pub min: f32,
+    pub max: f32,
+    pub mean_square: f32,
+
+    /// Number of samples in the run.
+    pub count: usize,
+}
+
+impl Envelope {
+    fn merge(self, other: Envelope) -> Envelope {
+        // Runs at the end of a level can be shorter, so weight by length.
+        let count = self.count + other.count;
+        Envelope {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+            mean_square: (self.mean_square * self.count as f32
+                + other.mean_square * other.count as f32)
+                / count as f32,
+            count,
+        }
+    }
+}
+
+/// Mip pyramid of a sample buffer. Each level summarizes pairs of entries
+/// of the level below, so any zoom can be drawn from about one entry per
+/// pixel.
+pub struct Waveform {
+    pub levels: Vec<Vec<Envelope>>,
+}
+
+impl Waveform {
+    pub fn new(samples: &[f32]) -> Self {
+        let base: Vec<Envelope> = samples
+            .iter()
+            .map(|s| Envelope {
+                min: *s,
+                max: *s,
+                mean_square: s * s,
+                count: 1,
+            })
+            .collect();
+
+        let mut levels = vec![base];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| pair.iter().copied().reduce(Envelope::merge).unwrap())
+                .collect();
+            levels.push(level);
+        }
+
+        Self { levels }
+    }
+
+    /// Number of samples.
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Finest level with fewer than two entries per pixel.
+    pub fn level(&self, samples_per_pixel: f32) -> usize {
+        let level = samples_per_pixel.max(1.0).log2().floor() as usize;
+        level.min(self.levels.len() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_pyramid() {
+        let w = Waveform::new(&[0.0, 1.0, -1.0, 0.5, 0.25]);
+        assert_eq!(w.len(), 5);
+        assert_eq!(w.levels.len(), 4);
+        assert_eq!(w.levels[1].len(), 3);
+        assert_eq!(
+            w.levels[1][0],
+            Envelope {
+                min: 0.0,
+                max: 1.0,
+                mean_square: 0.5,
+                count: 2,
+            }
+        );
+        assert_eq!(w.levels[1][2].max, 0.25);
+        assert_eq!(w.levels[3][0].min, -1.0);
+        assert_eq!(w.levels[3][0].max, 1.0);
+
+        // The short run at the end counts for less.
+        let ms = (0.0 + 1.0 + 1.0 + 0.25 + 0.0625) / 5.0;
+        assert!((w.levels[3][0].mean_square - ms).abs() < 1e-6);
+        assert_eq!(w.levels[3][0].count, 5);
+    }
+
+    #[test]
+    fn test_level() {
+        let w = Waveform::new(&[0.0; 1000]);
+        assert_eq!(w.level(0.5), 0);
+        assert_eq!(w.level(1.9), 0);
+        assert_eq!(w.level(2.0), 1);
+        assert_eq!(w.level(100.0), 6);
+        assert_eq!(w.level(1e6), w.levels.len() - 1);
+    }
+}
@@ -228,3 +228,14 @@ pub fn png_not_black(path: &str) -> bool {
 
     false
 }
+
+pub fn png_pixel(path: &str, x: usize, y: usize) -> [u8; 4] {
+    let decoder = png::Decoder::new(File::open(path).unwrap());
+    let mut reader = decoder.read_info().unwrap();
+
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+
+    let i = y * info.line_size + x * 4;
+    [buf[i], buf[i + 1], buf[i + 2], buf[i + 3]]
+}
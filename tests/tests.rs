@@ -124,6 +124,8 @@ fn stroke_rect_gradient() {
         [200.0, 200.0].into(),
         10.0,
         4.0,
+        StrokeAlign::Center,
+        Dash::SOLID,
         paint,
     );
 
@@ -156,8 +158,10 @@ fn stroke_arc_gradient() {
         [200.0, 200.0],
         100.0,
         4.0,
+        StrokeAlign::Center,
         0.0,
         std::f32::consts::PI / 2.0,
+        Dash::SOLID,
         paint,
     );
 
@@ -180,7 +184,14 @@ fn segment_stroke_gradient() {
         0.0,
     );
 
-    vger.stroke_segment([100.0, 100.0], [200.0, 200.0], 4.0, paint);
+    vger.stroke_segment(
+        [100.0, 100.0],
+        [200.0, 200.0],
+        4.0,
+        LineCap::Round,
+        Dash::SOLID,
+        paint,
+    );
 
     render_test(
         &mut vger,
@@ -207,7 +218,15 @@ fn bezier_stroke_gradient() {
         0.0,
     );
 
-    vger.stroke_bezier([100.0, 100.0], [150.0, 200.0], [200.0, 200.0], 4.0, paint);
+    vger.stroke_bezier(
+        [100.0, 100.0],
+        [150.0, 200.0],
+        [200.0, 200.0],
+        4.0,
+        LineCap::Round,
+        Dash::SOLID,
+        paint,
+    );
 
     render_test(
         &mut vger,
@@ -218,6 +237,335 @@ fn bezier_stroke_gradient() {
     );
 }
 
+#[test]
+fn curve_stroke() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let paint = vger.linear_gradient(
+        [100.0, 100.0],
+        [400.0, 100.0],
+        Color::CYAN,
+        Color::MAGENTA,
+        0.0,
+    );
+
+    let cvs: Vec<LocalPoint> = (0..=8)
+        .map(|i| {
+            let y = [256.0, 156.0, 256.0, 356.0][i % 4];
+            LocalPoint::new(100.0 + 37.5 * (i as f32), y)
+        })
+        .collect();
+
+    vger.stroke_curve(&cvs, 4.0, paint);
+
+    let png_name = "curve_stroke.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+    assert!(png_not_black(png_name));
+}
+
+#[test]
+fn variable_curve() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    let cvs = [
+        LocalPoint::new(50.0, 256.0),
+        LocalPoint::new(150.0, 256.0),
+        LocalPoint::new(250.0, 256.0),
+        LocalPoint::new(350.0, 256.0),
+        LocalPoint::new(450.0, 256.0),
+    ];
+    vger.stroke_variable_curve(&cvs, &[2.0, 10.0, 40.0, 10.0, 2.0], cyan);
+
+    // Missing widths draw nothing.
+    let cvs = [
+        cvs[0] + LocalVector::new(0.0, 150.0),
+        cvs[2] + LocalVector::new(0.0, 150.0),
+        cvs[4] + LocalVector::new(0.0, 150.0),
+    ];
+    vger.stroke_variable_curve(&cvs, &[20.0, 20.0], cyan);
+
+    let png_name = "variable_curve.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // Thick in the middle, thin at the ends.
+    assert!(png_pixel(png_name, 250, 271)[1] > 0);
+    assert_eq!(png_pixel(png_name, 250, 281)[1], 0);
+    assert!(png_pixel(png_name, 60, 256)[1] > 0);
+    assert_eq!(png_pixel(png_name, 60, 271)[1], 0);
+    assert_eq!(png_pixel(png_name, 250, 406)[1], 0);
+}
+
+#[test]
+fn polyline() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    let trace = |x: f32| LocalPoint::new(x, 128.0 + 60.0 * (x / 20.0).sin());
+    let points: Vec<LocalPoint> = (0..1000).map(|i| trace(20.0 + 0.47 * i as f32)).collect();
+    vger.stroke_polyline(&points, 2.0, StrokeStyle::default(), cyan);
+
+    let zigzag = [
+        LocalPoint::new(50.0, 400.0),
+        LocalPoint::new(150.0, 300.0),
+        LocalPoint::new(250.0, 400.0),
+    ];
+    let style = |join| StrokeStyle {
+        join,
+        cap: LineCap::Butt,
+        ..Default::default()
+    };
+    vger.stroke_polyline(&zigzag, 10.0, style(LineJoin::Miter), cyan);
+    let zigzag: Vec<LocalPoint> = zigzag
+        .iter()
+        .map(|p| *p + LocalVector::new(250.0, 0.0))
+        .collect();
+    vger.stroke_polyline(&zigzag, 10.0, style(LineJoin::Bevel), cyan);
+
+    // A translucent line split into bands, with repeated points.
+    let translucent = vger.color_paint(Color::new(1.0, 1.0, 1.0, 0.5));
+    let line: Vec<LocalPoint> = (0..200)
+        .map(|i| LocalPoint::new(20.0 + 4.7 * (i / 2) as f32, 480.0))
+        .collect();
+    vger.stroke_polyline(&line, 10.0, StrokeStyle::default(), translucent);
+
+    let png_name = "polyline.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // The trace is continuous where it's split between prims.
+    for i in [0, 32, 64, 500, 999] {
+        let p = points[i];
+        assert!(png_pixel(png_name, p.x as usize, p.y as usize)[1] > 0);
+    }
+    assert_eq!(png_pixel(png_name, 100, 250)[1], 0);
+
+    // Miter and bevel joins.
+    assert!(png_pixel(png_name, 150, 294)[1] > 0);
+    assert_eq!(png_pixel(png_name, 400, 294)[1], 0);
+
+    // Pixels where bands meet are blended once.
+    let color = png_pixel(png_name, 30, 480);
+    assert!(color[0] > 0);
+    for x in 30..480 {
+        assert_eq!(png_pixel(png_name, x, 480), color);
+    }
+}
+
+#[test]
+fn fill_area() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    let peak = |x: f32| 300.0 - 150.0 * (-((x - 250.0) / 60.0).powi(2)).exp();
+    let points: Vec<LocalPoint> = (0..400)
+        .map(|i| {
+            let x = 50.0 + i as f32;
+            LocalPoint::new(x, peak(x))
+        })
+        .collect();
+    vger.fill_area(&points, 300.0, cyan);
+
+    // Below the baseline.
+    let dip = [
+        LocalPoint::new(50.0, 450.0),
+        LocalPoint::new(250.0, 490.0),
+        LocalPoint::new(450.0, 450.0),
+    ];
+    vger.fill_area(&dip, 450.0, cyan);
+
+    // Out of order points draw nothing.
+    let reversed = [
+        LocalPoint::new(450.0, 100.0),
+        LocalPoint::new(250.0, 20.0),
+        LocalPoint::new(50.0, 100.0),
+    ];
+    vger.fill_area(&reversed, 100.0, cyan);
+
+    let png_name = "fill_area.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    assert!(png_pixel(png_name, 250, 250)[1] > 0);
+    assert_eq!(png_pixel(png_name, 250, 140)[1], 0);
+    assert_eq!(png_pixel(png_name, 250, 310)[1], 0);
+    assert!(png_pixel(png_name, 250, 470)[1] > 0);
+    assert_eq!(png_pixel(png_name, 250, 440)[1], 0);
+    assert_eq!(png_pixel(png_name, 250, 80)[1], 0);
+}
+
+#[test]
+fn waveform() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+    let white = vger.color_paint(Color::WHITE);
+
+    let samples: Vec<f32> = (0..48000)
+        .map(|i| 0.5 * (i as f32 * 440.0 * std::f32::consts::TAU / 48000.0).sin())
+        .collect();
+    let sine = vger.create_waveform(&samples);
+    vger.draw_waveform(
+        sine,
+        0..samples.len(),
+        euclid::rect(0.0, 100.0, 512.0, 300.0),
+        cyan,
+        white,
+    );
+
+    // Zoomed in far enough to see single samples.
+    vger.draw_waveform(
+        sine,
+        1000..1200,
+        euclid::rect(0.0, 420.0, 512.0, 80.0),
+        cyan,
+        white,
+    );
+
+    let silence = vger.create_waveform(&[0.0; 1000]);
+    vger.draw_waveform(
+        silence,
+        0..1000,
+        euclid::rect(0.0, 0.0, 512.0, 40.0),
+        cyan,
+        white,
+    );
+
+    // Deleted waveforms draw nothing.
+    vger.delete_waveform(sine);
+    vger.draw_waveform(
+        sine,
+        0..1000,
+        euclid::rect(0.0, 40.0, 512.0, 50.0),
+        cyan,
+        white,
+    );
+
+    let png_name = "waveform.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // The peaks of the envelope, and the RMS inside it.
+    assert_eq!(png_pixel(png_name, 256, 178)[..3], [0, 255, 255]);
+    assert_eq!(png_pixel(png_name, 256, 165)[..3], [0, 0, 0]);
+    assert_eq!(png_pixel(png_name, 256, 240)[..3], [255, 255, 255]);
+
+    // Silence is still a line.
+    assert!(png_pixel(png_name, 256, 20)[1] > 0);
+    assert_eq!(png_pixel(png_name, 256, 10)[1], 0);
+    assert_eq!(png_pixel(png_name, 256, 65)[1], 0);
+}
+
+#[test]
+fn fill_grid() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+    let white = vger.color_paint(Color::WHITE);
+
+    vger.fill_grid(
+        euclid::rect(0.0, 0.0, 512.0, 256.0),
+        16.0,
+        2.0,
+        4,
+        cyan,
+        white,
+    );
+
+    // Zoomed out so the minor lines are too close together to show.
+    vger.save();
+    vger.translate([0.0, 300.0]);
+    vger.scale([0.1, 0.1]);
+    vger.fill_grid(
+        euclid::rect(0.0, 0.0, 5120.0, 2120.0),
+        16.0,
+        2.0,
+        4,
+        cyan,
+        white,
+    );
+    vger.restore();
+
+    let png_name = "fill_grid.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    assert_eq!(png_pixel(png_name, 63, 10)[..3], [255, 255, 255]);
+    assert_eq!(png_pixel(png_name, 15, 10)[..3], [0, 255, 255]);
+    assert_eq!(png_pixel(png_name, 8, 8)[..3], [0, 0, 0]);
+    assert_eq!(png_pixel(png_name, 3, 400)[..3], [0, 0, 0]);
+}
+
+#[test]
+fn fill_dot_grid() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+    let white = vger.color_paint(Color::WHITE);
+
+    vger.fill_dot_grid(
+        euclid::rect(0.0, 0.0, 512.0, 512.0),
+        16.0,
+        3.0,
+        4,
+        cyan,
+        white,
+    );
+
+    let png_name = "fill_dot_grid.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    assert_eq!(png_pixel(png_name, 64, 64)[..3], [255, 255, 255]);
+    assert_eq!(png_pixel(png_name, 16, 16)[..3], [0, 255, 255]);
+    assert_eq!(png_pixel(png_name, 64, 72)[..3], [0, 0, 0]);
+    assert_eq!(png_pixel(png_name, 8, 8)[..3], [0, 0, 0]);
+}
+
+#[test]
+fn wire_stroke() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+    let cyan = vger.color_paint(Color::CYAN);
+
+    vger.stroke_wire([50.0, 100.0], [450.0, 150.0], 4.0, cyan);
+    vger.stroke_wire([100.0, 300.0], [150.0, 200.0], 4.0, cyan);
+    vger.stroke_wire([300.0, 250.0], [300.0, 400.0], 4.0, cyan);
+
+    let png_name = "wire_stroke.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+    assert!(png_not_black(png_name));
+}
+
 fn rand2<T: rand::Rng>(rng: &mut T) -> LocalPoint {
     LocalPoint::new(rng.gen_range(0.0..512.0), rng.gen_range(0.0..512.0))
 }
@@ -243,13 +591,846 @@ fn path_fill() {
     }
 
     vger.quad_to(rand2(&mut rng), start);
-    vger.fill(paint);
+    vger.fill(paint, FillRule::EvenOdd);
 
     let png_name = "path_fill.png";
     render_test(&mut vger, &device, &queue, png_name, true);
     assert!(png_not_black(png_name));
 }
 
+#[test]
+fn path_fill_cubic() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let paint = vger.linear_gradient([0.0, 0.0], [512.0, 512.0], Color::CYAN, Color::MAGENTA, 0.0);
+
+    // Heart shape.
+    vger.move_to([256.0, 150.0]);
+    vger.cubic_to([256.0, 50.0], [56.0, 100.0], [100.0, 250.0]);
+    vger.cubic_to([130.0, 350.0], [256.0, 400.0], [256.0, 450.0]);
+    vger.cubic_to([256.0, 400.0], [382.0, 350.0], [412.0, 250.0]);
+    vger.cubic_to([456.0, 100.0], [256.0, 50.0], [256.0, 150.0]);
+    vger.fill(paint, FillRule::NonZero);
+
+    let png_name = "path_fill_cubic.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+    assert!(png_not_black(png_name));
+}
+
+#[test]
+fn path_fill_shapes() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    vger.add_rect(euclid::rect(20.0, 20.0, 100.0, 60.0));
+    vger.fill(cyan, FillRule::NonZero);
+
+    vger.add_rounded_rect(euclid::rect(150.0, 20.0, 100.0, 60.0), 15.0);
+    vger.fill(cyan, FillRule::NonZero);
+
+    vger.add_circle([330.0, 50.0], 30.0);
+    vger.fill(cyan, FillRule::NonZero);
+
+    vger.add_ellipse([440.0, 50.0], [50.0, 25.0]);
+    vger.fill(cyan, FillRule::NonZero);
+
+    // Pie wedge built from lines and an arc.
+    vger.move_to([100.0, 250.0]);
+    vger.arc([100.0, 250.0], 80.0, 0.0, 1.5 * std::f32::consts::PI);
+    vger.close_path();
+    vger.fill(cyan, FillRule::NonZero);
+
+    // Triangle with one rounded corner.
+    vger.move_to([250.0, 350.0]);
+    vger.arc_to([350.0, 150.0], [450.0, 350.0], 40.0);
+    vger.line_to([450.0, 350.0]);
+    vger.close_path();
+    vger.fill(cyan, FillRule::NonZero);
+
+    // Half disk without a move_to, which starts at the arc.
+    vger.arc([400.0, 450.0], 40.0, 0.0, std::f32::consts::PI);
+    vger.fill(cyan, FillRule::NonZero);
+
+    let png_name = "path_fill_shapes.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+    assert!(png_not_black(png_name));
+    assert_eq!(png_pixel(png_name, 400, 470)[..3], [0, 255, 255]);
+
+    // No line from the origin.
+    assert_eq!(png_pixel(png_name, 200, 230)[..3], [0, 0, 0]);
+}
+
+#[test]
+fn path_fill_holes() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let paint = vger.linear_gradient([0.0, 0.0], [512.0, 512.0], Color::CYAN, Color::MAGENTA, 0.0);
+
+    // Donut.
+    vger.add_circle([150.0, 150.0], 100.0);
+    vger.add_circle([150.0, 150.0], 50.0);
+    vger.fill(paint, FillRule::EvenOdd);
+
+    // Square ring from open contours.
+    vger.move_to([300.0, 300.0]);
+    vger.line_to([450.0, 300.0]);
+    vger.line_to([450.0, 450.0]);
+    vger.line_to([300.0, 450.0]);
+    vger.move_to([340.0, 340.0]);
+    vger.line_to([410.0, 340.0]);
+    vger.line_to([410.0, 410.0]);
+    vger.line_to([340.0, 410.0]);
+    vger.fill(paint, FillRule::EvenOdd);
+
+    let png_name = "path_fill_holes.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+    assert!(png_not_black(png_name));
+}
+
+#[test]
+fn path_fill_rule() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    // Overlapping contours going the same direction.
+    vger.add_circle([100.0, 128.0], 80.0);
+    vger.add_circle([180.0, 128.0], 80.0);
+    vger.fill(cyan, FillRule::NonZero);
+
+    vger.add_circle([100.0, 384.0], 80.0);
+    vger.add_circle([180.0, 384.0], 80.0);
+    vger.fill(cyan, FillRule::EvenOdd);
+
+    // A contour wound twice.
+    vger.move_to([300.0, 50.0]);
+    for _ in 0..2 {
+        vger.line_to([450.0, 50.0]);
+        vger.line_to([450.0, 200.0]);
+        vger.line_to([300.0, 200.0]);
+        vger.line_to([300.0, 50.0]);
+    }
+    vger.fill(cyan, FillRule::NonZero);
+
+    let png_name = "path_fill_rule.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // The overlap is inside for non-zero and a hole for even-odd.
+    assert!(png_pixel(png_name, 140, 128)[1] > 0);
+    assert_eq!(png_pixel(png_name, 140, 384)[1], 0);
+    assert!(png_pixel(png_name, 60, 384)[1] > 0);
+    assert!(png_pixel(png_name, 375, 125)[1] > 0);
+}
+
+#[test]
+fn line_caps() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    let caps = [LineCap::Butt, LineCap::Round, LineCap::Square];
+
+    for (i, cap) in caps.iter().enumerate() {
+        let x = 100.0 + 150.0 * i as f32;
+        vger.stroke_segment([x, 100.0], [x, 200.0], 20.0, *cap, Dash::SOLID, cyan);
+        vger.stroke_bezier(
+            [x - 50.0, 300.0],
+            [x, 450.0],
+            [x + 50.0, 300.0],
+            20.0,
+            *cap,
+            Dash::SOLID,
+            cyan,
+        );
+    }
+
+    let png_name = "line_caps.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // Butt caps end flush, round and square caps extend past the end.
+    assert_eq!(png_pixel(png_name, 100, 95)[1], 0);
+    assert!(png_pixel(png_name, 250, 95)[1] > 0);
+    assert!(png_pixel(png_name, 400, 95)[1] > 0);
+
+    // Only square caps fill the corners.
+    assert_eq!(png_pixel(png_name, 258, 92)[1], 0);
+    assert!(png_pixel(png_name, 408, 92)[1] > 0);
+}
+
+#[test]
+fn dashed_strokes() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    let dash = Dash::new(&[20.0, 10.0], 0.0);
+    vger.stroke_segment([50.0, 50.0], [450.0, 50.0], 8.0, LineCap::Butt, dash, cyan);
+    vger.stroke_rect(
+        LocalPoint::new(50.0, 100.0),
+        LocalPoint::new(200.0, 200.0),
+        10.0,
+        4.0,
+        StrokeAlign::Center,
+        dash,
+        cyan,
+    );
+    vger.stroke_arc(
+        [350.0, 150.0],
+        50.0,
+        4.0,
+        StrokeAlign::Center,
+        0.0,
+        2.0,
+        dash,
+        cyan,
+    );
+    vger.stroke_bezier(
+        [50.0, 300.0],
+        [250.0, 450.0],
+        [450.0, 300.0],
+        4.0,
+        LineCap::Butt,
+        dash,
+        cyan,
+    );
+
+    // Dotted line.
+    // Invalid patterns draw solid strokes.
+    let invalid = Dash::new(&[10.0, -5.0], 0.0);
+    vger.stroke_segment(
+        [50.0, 400.0],
+        [450.0, 400.0],
+        8.0,
+        LineCap::Butt,
+        invalid,
+        cyan,
+    );
+
+    let dots = Dash::new(&[0.0, 16.0], 0.0);
+    vger.stroke_segment(
+        [50.0, 450.0],
+        [450.0, 450.0],
+        8.0,
+        LineCap::Round,
+        dots,
+        cyan,
+    );
+
+    let png_name = "dashed_strokes.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // Dashes and gaps along the segment.
+    assert!(png_pixel(png_name, 60, 50)[1] > 0);
+    assert_eq!(png_pixel(png_name, 75, 50)[1], 0);
+    assert!(png_pixel(png_name, 90, 50)[1] > 0);
+
+    assert!(png_pixel(png_name, 75, 400)[1] > 0);
+
+    // Dots centered every 16 pixels.
+    assert!(png_pixel(png_name, 66, 450)[1] > 0);
+    assert_eq!(png_pixel(png_name, 74, 450)[1], 0);
+}
+
+#[test]
+fn ellipse() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+    let paint = vger.linear_gradient(
+        [300.0, 100.0],
+        [500.0, 200.0],
+        Color::CYAN,
+        Color::MAGENTA,
+        0.0,
+    );
+
+    vger.fill_ellipse([150.0, 150.0], [120.0, 60.0], cyan);
+    vger.stroke_ellipse([400.0, 150.0], [60.0, 120.0], 8.0, paint);
+    vger.stroke_ellipse([256.0, 400.0], [200.0, 50.0], 2.0, cyan);
+
+    let png_name = "ellipse.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    assert!(png_pixel(png_name, 260, 150)[1] > 0);
+    assert_eq!(png_pixel(png_name, 150, 80)[1], 0);
+    assert!(png_pixel(png_name, 400, 30)[2] > 0);
+    assert_eq!(png_pixel(png_name, 400, 150)[2], 0);
+}
+
+#[test]
+fn sector() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    // Pie slice.
+    vger.fill_sector([128.0, 128.0], 0.0, 100.0, 0.0, 1.0, LineCap::Butt, cyan);
+
+    // Knob value indicators with each kind of end.
+    let caps = [LineCap::Butt, LineCap::Round, LineCap::Square];
+    for (i, cap) in caps.iter().enumerate() {
+        let x = 100.0 + 150.0 * i as f32;
+        vger.fill_sector(
+            [x, 384.0],
+            40.0,
+            60.0,
+            -std::f32::consts::FRAC_PI_2,
+            2.0,
+            *cap,
+            cyan,
+        );
+    }
+
+    let png_name = "sector.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    assert!(png_pixel(png_name, 60, 128)[1] > 0);
+    assert_eq!(png_pixel(png_name, 200, 128)[1], 0);
+    assert!(png_pixel(png_name, 100, 334)[1] > 0);
+    assert_eq!(png_pixel(png_name, 100, 384)[1], 0);
+}
+
+#[test]
+fn stroke_align() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    vger.stroke_rect(
+        LocalPoint::new(50.0, 50.0),
+        LocalPoint::new(150.0, 150.0),
+        0.0,
+        10.0,
+        StrokeAlign::Inside,
+        Dash::SOLID,
+        cyan,
+    );
+    vger.stroke_rect(
+        LocalPoint::new(200.0, 50.0),
+        LocalPoint::new(300.0, 150.0),
+        0.0,
+        10.0,
+        StrokeAlign::Outside,
+        Dash::SOLID,
+        cyan,
+    );
+    vger.stroke_circle(
+        [128.0, 350.0],
+        60.0,
+        10.0,
+        StrokeAlign::Inside,
+        Dash::SOLID,
+        cyan,
+    );
+    vger.stroke_circle(
+        [384.0, 350.0],
+        60.0,
+        10.0,
+        StrokeAlign::Outside,
+        Dash::SOLID,
+        cyan,
+    );
+
+    let png_name = "stroke_align.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    assert!(png_pixel(png_name, 52, 100)[1] > 0);
+    assert_eq!(png_pixel(png_name, 47, 100)[1], 0);
+    assert!(png_pixel(png_name, 197, 100)[1] > 0);
+    assert_eq!(png_pixel(png_name, 203, 100)[1], 0);
+    assert!(png_pixel(png_name, 185, 350)[1] > 0);
+    assert_eq!(png_pixel(png_name, 191, 350)[1], 0);
+    assert!(png_pixel(png_name, 447, 350)[1] > 0);
+    assert_eq!(png_pixel(png_name, 441, 350)[1], 0);
+}
+
+#[test]
+fn border_rect() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    // Side paints needn't be consecutive.
+    let white = vger.color_paint(Color::WHITE);
+    let blue = vger.color_paint(Color::new(0.0, 0.0, 1.0, 1.0));
+    let red = vger.color_paint(Color::new(1.0, 0.0, 0.0, 1.0));
+    let green = vger.color_paint(Color::new(0.0, 1.0, 0.0, 1.0));
+
+    let widths = BorderWidths {
+        top: 10.0,
+        right: 20.0,
+        bottom: 30.0,
+        left: 40.0,
+    };
+
+    vger.border_rect(
+        euclid::rect(100.0, 100.0, 300.0, 300.0),
+        0.0,
+        widths,
+        [red, green, blue, white],
+    );
+    vger.border_rect(
+        euclid::rect(20.0, 420.0, 200.0, 80.0),
+        30.0,
+        8.0,
+        [red, green, blue, white],
+    );
+
+    let png_name = "border_rect.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    assert_eq!(png_pixel(png_name, 250, 105)[..3], [255, 0, 0]);
+    assert_eq!(png_pixel(png_name, 390, 250)[..3], [0, 255, 0]);
+    assert_eq!(png_pixel(png_name, 250, 385)[..3], [0, 0, 255]);
+    assert_eq!(png_pixel(png_name, 120, 250)[..3], [255, 255, 255]);
+    assert_eq!(png_pixel(png_name, 250, 250)[..3], [0, 0, 0]);
+
+    // Mitered corner.
+    assert_eq!(png_pixel(png_name, 130, 103)[..3], [255, 0, 0]);
+    assert_eq!(png_pixel(png_name, 105, 108)[..3], [255, 255, 255]);
+}
+
+#[test]
+fn stroke_gradient() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    let paint = vger.stroke_gradient(red, blue, 0.0);
+
+    vger.stroke_segment(
+        [50.0, 50.0],
+        [450.0, 50.0],
+        10.0,
+        LineCap::Butt,
+        Dash::SOLID,
+        paint,
+    );
+    vger.stroke_bezier(
+        [50.0, 250.0],
+        [250.0, 50.0],
+        [450.0, 250.0],
+        10.0,
+        LineCap::Butt,
+        Dash::SOLID,
+        paint,
+    );
+    vger.stroke_wire([50.0, 300.0], [450.0, 300.0], 10.0, paint);
+
+    // Long enough to be split into bands.
+    let points: Vec<LocalPoint> = (0..=100)
+        .map(|i| LocalPoint::new(50.0 + 4.0 * i as f32, 400.0))
+        .collect();
+    vger.stroke_polyline(&points, 10.0, StrokeStyle::default(), paint);
+
+    // The gradient runs through both subpaths.
+    vger.move_to([50.0, 450.0]);
+    vger.line_to([240.0, 450.0]);
+    vger.move_to([260.0, 450.0]);
+    vger.line_to([450.0, 450.0]);
+    vger.stroke(10.0, StrokeStyle::default(), paint);
+
+    let png_name = "stroke_gradient.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // Red at the start and blue at the end.
+    let start = png_pixel(png_name, 55, 50);
+    let end = png_pixel(png_name, 445, 50);
+    assert!(start[0] > start[2]);
+    assert!(end[2] > end[0]);
+
+    let start = png_pixel(png_name, 52, 248);
+    let end = png_pixel(png_name, 448, 248);
+    assert!(start[0] > start[2]);
+    assert!(end[2] > end[0]);
+
+    for y in [400, 450] {
+        let start = png_pixel(png_name, 55, y);
+        let end = png_pixel(png_name, 445, y);
+        assert!(start[0] > start[2]);
+        assert!(end[2] > end[0]);
+    }
+}
+
+#[test]
+fn corner_radii() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    let tab = CornerRadii {
+        top_left: 40.0,
+        top_right: 40.0,
+        bottom_right: 0.0,
+        bottom_left: 0.0,
+    };
+
+    vger.fill_rect(euclid::rect(50.0, 50.0, 200.0, 150.0), tab, cyan, 0.0);
+    vger.fill_rect(euclid::rect(300.0, 50.0, 150.0, 150.0), tab, cyan, 10.0);
+    vger.stroke_rect(
+        LocalPoint::new(50.0, 300.0),
+        LocalPoint::new(250.0, 450.0),
+        CornerRadii {
+            top_left: 0.0,
+            top_right: 60.0,
+            bottom_right: 10.0,
+            bottom_left: 30.0,
+        },
+        4.0,
+        StrokeAlign::Center,
+        Dash::SOLID,
+        cyan,
+    );
+
+    let png_name = "corner_radii.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // Rounded top corners, square bottom corners.
+    assert_eq!(png_pixel(png_name, 52, 52)[1], 0);
+    assert!(png_pixel(png_name, 52, 198)[1] > 0);
+
+    // Square top left corner of the stroke.
+    assert!(png_pixel(png_name, 50, 300)[1] > 0);
+    assert_eq!(png_pixel(png_name, 250, 300)[1], 0);
+}
+
+#[test]
+fn polygon_star() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    // Play button.
+    vger.fill_polygon([128.0, 128.0], 80.0, 3, 0.0, 8.0, cyan);
+    vger.stroke_polygon([384.0, 128.0], 80.0, 6, 0.0, 0.0, 4.0, cyan);
+    vger.fill_star(
+        [128.0, 384.0],
+        100.0,
+        40.0,
+        5,
+        -std::f32::consts::FRAC_PI_2,
+        cyan,
+    );
+    vger.stroke_star([384.0, 384.0], 100.0, 60.0, 8, 0.0, 4.0, cyan);
+
+    let png_name = "polygon_star.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    assert!(png_pixel(png_name, 128, 128)[1] > 0);
+    assert!(png_pixel(png_name, 200, 128)[1] > 0);
+    assert_eq!(png_pixel(png_name, 60, 128)[1], 0);
+    assert_eq!(png_pixel(png_name, 384, 128)[1], 0);
+    assert!(png_pixel(png_name, 128, 294)[1] > 0);
+}
+
+#[test]
+fn shadows() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    vger.set_shadow([10.0, 10.0], 8.0, Color::MAGENTA);
+    vger.fill_circle([128.0, 128.0], 60.0, cyan);
+    vger.stroke_arc(
+        [384.0, 128.0],
+        60.0,
+        8.0,
+        StrokeAlign::Center,
+        0.0,
+        2.0,
+        Dash::SOLID,
+        cyan,
+    );
+    vger.fill_rect(euclid::rect(64.0, 320.0, 128.0, 128.0), 10.0, cyan, 0.0);
+    vger.reset_shadow();
+
+    // Shadows are scoped by save and restore.
+    vger.save();
+    vger.set_shadow([0.0, 20.0], 4.0, Color::MAGENTA);
+    vger.move_to([300.0, 240.0]);
+    vger.line_to([460.0, 240.0]);
+    vger.line_to([460.0, 270.0]);
+    vger.line_to([300.0, 270.0]);
+    vger.fill(cyan, FillRule::NonZero);
+    vger.restore();
+
+    vger.fill_circle([384.0, 384.0], 60.0, cyan);
+
+    let png_name = "shadows.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // Shadows show past the bottom right of the shapes only.
+    assert!(png_pixel(png_name, 128 + 50, 128 + 50)[0] > 0);
+    assert_eq!(png_pixel(png_name, 40, 128)[0], 0);
+    assert!(png_pixel(png_name, 194, 450)[0] > 0);
+    assert_eq!(png_pixel(png_name, 384 + 50, 384 + 50)[0], 0);
+
+    // Path fill shadows are blurred past the bottom of the path.
+    assert!(png_pixel(png_name, 380, 292)[0] > 0);
+}
+
+#[test]
+fn box_shadow() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+    let magenta = vger.color_paint(Color::MAGENTA);
+
+    let rect = euclid::rect(64.0, 64.0, 128.0, 128.0);
+    vger.box_shadow(
+        rect,
+        10.0,
+        BoxShadow {
+            offset: [20.0, 20.0].into(),
+            blur_radius: 4.0,
+            spread: 10.0,
+            inset: false,
+        },
+        magenta,
+    );
+    vger.fill_rect(rect, 10.0, cyan, 0.0);
+
+    let tab = CornerRadii {
+        top_left: 30.0,
+        top_right: 30.0,
+        bottom_right: 0.0,
+        bottom_left: 0.0,
+    };
+    let rect = euclid::rect(320.0, 64.0, 128.0, 128.0);
+    vger.fill_rect(rect, tab, cyan, 0.0);
+    vger.box_shadow(
+        rect,
+        tab,
+        BoxShadow {
+            offset: [10.0, 10.0].into(),
+            blur_radius: 4.0,
+            spread: 0.0,
+            inset: true,
+        },
+        magenta,
+    );
+
+    // Negative spread with no blur.
+    vger.box_shadow(
+        euclid::rect(64.0, 320.0, 128.0, 128.0),
+        0.0,
+        BoxShadow {
+            offset: [0.0, 0.0].into(),
+            blur_radius: 0.0,
+            spread: -20.0,
+            inset: false,
+        },
+        magenta,
+    );
+
+    let png_name = "box_shadow.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // Outer shadow is offset and spread past the box.
+    assert!(png_pixel(png_name, 215, 215)[0] > 0);
+    assert_eq!(png_pixel(png_name, 50, 50)[0], 0);
+
+    // Inset shadow is along the top left inside the box only.
+    assert!(png_pixel(png_name, 322, 128)[0] > 0);
+    assert_eq!(png_pixel(png_name, 420, 150)[0], 0);
+    assert_eq!(png_pixel(png_name, 318, 128)[0], 0);
+
+    // Negative spread shrinks the shadow.
+    assert_eq!(png_pixel(png_name, 74, 384)[0], 0);
+    assert!(png_pixel(png_name, 128, 384)[0] > 0);
+}
+
+#[test]
+fn glow() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let glow = vger.linear_gradient([0.0, 0.0], [512.0, 0.0], Color::CYAN, Color::CYAN, 20.0);
+
+    // LED.
+    vger.fill_circle([128.0, 128.0], 20.0, glow);
+
+    // Cable.
+    vger.stroke_wire([256.0, 100.0], [450.0, 100.0], 4.0, glow);
+
+    // Path fills glow above and below.
+    vger.move_to([100.0, 300.0]);
+    vger.line_to([400.0, 300.0]);
+    vger.line_to([250.0, 400.0]);
+    vger.fill(glow, FillRule::NonZero);
+
+    let png_name = "glow.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    // The glow fades out past the edge.
+    assert!(png_pixel(png_name, 128, 100)[1] > 0);
+    assert!(png_pixel(png_name, 128, 100)[1] < png_pixel(png_name, 128, 128)[1]);
+    assert_eq!(png_pixel(png_name, 128, 80)[1], 0);
+    assert!(png_pixel(png_name, 250, 290)[1] > 0);
+    assert!(png_pixel(png_name, 250, 410)[1] > 0);
+}
+
+#[test]
+fn mesh() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let square = [
+        LocalPoint::new(0.0, 0.0),
+        LocalPoint::new(100.0, 0.0),
+        LocalPoint::new(100.0, 100.0),
+        LocalPoint::new(0.0, 100.0),
+    ];
+
+    vger.save();
+    vger.translate([100.0, 100.0]);
+    vger.draw_mesh(&square, &[0, 1, 2, 0, 2, 3], &[red; 4]);
+    vger.restore();
+
+    // Prims drawn later go on top.
+    let cyan = vger.color_paint(Color::CYAN);
+    vger.fill_rect(euclid::rect(150.0, 150.0, 100.0, 100.0), 0.0, cyan, 0.0);
+
+    // Higher z-index goes on top regardless of drawing order.
+    vger.set_z_index(1);
+    let triangle = [
+        LocalPoint::new(300.0, 300.0),
+        LocalPoint::new(450.0, 300.0),
+        LocalPoint::new(300.0, 450.0),
+    ];
+    vger.draw_mesh(&triangle, &[0, 1, 2], &[Color::MAGENTA; 3]);
+
+    vger.set_z_index(0);
+    let white = vger.color_paint(Color::WHITE);
+    vger.fill_rect(euclid::rect(300.0, 300.0, 100.0, 100.0), 0.0, white, 0.0);
+
+    let png_name = "mesh.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    assert_eq!(png_pixel(png_name, 125, 125)[..3], [255, 0, 0]);
+    assert_eq!(png_pixel(png_name, 175, 175)[..3], [0, 255, 255]);
+    assert_eq!(png_pixel(png_name, 50, 50)[..3], [0, 0, 0]);
+    assert_eq!(png_pixel(png_name, 320, 320)[..3], [255, 0, 255]);
+    assert_eq!(png_pixel(png_name, 390, 390)[..3], [255, 255, 255]);
+}
+
+#[test]
+fn path_stroke() {
+    let (device, queue) = block_on(setup());
+
+    let mut vger = Vger::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    vger.begin(512.0, 512.0, 1.0);
+
+    let cyan = vger.color_paint(Color::CYAN);
+
+    let joins = [LineJoin::Miter, LineJoin::Round, LineJoin::Bevel];
+    let caps = [LineCap::Butt, LineCap::Round, LineCap::Square];
+
+    for (i, (join, cap)) in joins.iter().zip(caps.iter()).enumerate() {
+        let y = 50.0 + 120.0 * i as f32;
+        vger.move_to([50.0, y]);
+        vger.line_to([150.0, y + 80.0]);
+        vger.line_to([250.0, y]);
+        vger.quad_to([300.0, y + 80.0], [350.0, y]);
+        vger.stroke(
+            12.0,
+            StrokeStyle {
+                join: *join,
+                cap: *cap,
+                ..Default::default()
+            },
+            cyan,
+        );
+    }
+
+    vger.add_rounded_rect(euclid::rect(380.0, 50.0, 100.0, 100.0), 20.0);
+    vger.add_circle([430.0, 300.0], 50.0);
+    vger.stroke(4.0, StrokeStyle::default(), cyan);
+
+    // Crossing subpaths with a translucent paint.
+    let translucent = vger.color_paint(Color::new(1.0, 1.0, 1.0, 0.5));
+    vger.move_to([50.0, 450.0]);
+    vger.line_to([250.0, 450.0]);
+    vger.move_to([150.0, 400.0]);
+    vger.line_to([150.0, 500.0]);
+    vger.stroke(12.0, StrokeStyle::default(), translucent);
+
+    let png_name = "path_stroke.png";
+    render_test(&mut vger, &device, &queue, png_name, false);
+
+    assert!(png_pixel(png_name, 100, 90)[1] > 0);
+    assert!(png_pixel(png_name, 380, 100)[1] > 0);
+    assert_eq!(png_pixel(png_name, 430, 100)[1], 0);
+    assert_eq!(png_pixel(png_name, 150, 450), png_pixel(png_name, 100, 450));
+}
+
 #[test]
 fn text() {
     let (device, queue) = block_on(setup());
@@ -332,7 +1513,15 @@ fn text_box() {
 
     let bounds = vger.text_bounds(lorem, 18, Some(448.0));
 
-    vger.stroke_rect(bounds.origin, bounds.max(), 10.0, 4.0, paint);
+    vger.stroke_rect(
+        bounds.origin,
+        bounds.max(),
+        10.0,
+        4.0,
+        StrokeAlign::Center,
+        Dash::SOLID,
+        paint,
+    );
 
     vger.text(lorem, 18, Color::WHITE, Some(448.0));
 
@@ -384,7 +1573,15 @@ fn test_scissor_text() {
 
     let bounds = vger.text_bounds(lorem, 18, Some(448.0));
 
-    vger.stroke_rect(bounds.origin, bounds.max(), 10.0, 4.0, paint);
+    vger.stroke_rect(
+        bounds.origin,
+        bounds.max(),
+        10.0,
+        4.0,
+        StrokeAlign::Center,
+        Dash::SOLID,
+        paint,
+    );
 
     vger.text(lorem, 18, Color::WHITE, Some(448.0));
 